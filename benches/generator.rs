@@ -0,0 +1,39 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use totp_sm_rs::utils::totp::generator::TotpGenerator;
+use totp_sm_rs::utils::totp::totp::{generate_totp_code, HashAlgorithm, TotpConfig};
+
+const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+/// 对比逐次调用与预先载入密钥的生成器（当前构建启用的全部哈希算法，以 `--all-features` 覆盖SHA3、BLAKE2与Streebog）
+fn bench_generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("totp");
+    for &hash_algorithm in HashAlgorithm::ALL {
+        let config = TotpConfig {
+            timestamp: Some(1111111109),
+            hash_algorithm,
+            ..TotpConfig::default()
+        };
+        group.bench_with_input(
+            BenchmarkId::new("generate_totp_code", hash_algorithm),
+            &config,
+            |b, config| b.iter(|| generate_totp_code(black_box(SECRET), Some(*config)).unwrap()),
+        );
+
+        let generator = TotpGenerator::new(SECRET, Some(config)).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("TotpGenerator::code_value", hash_algorithm),
+            &generator,
+            |b, generator| b.iter(|| generator.code_value(black_box(37037036))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("TotpGenerator::verify", hash_algorithm),
+            &generator,
+            |b, generator| b.iter(|| generator.verify(black_box("000000"), 1, 1)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_generation);
+criterion_main!(benches);
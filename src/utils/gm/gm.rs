@@ -0,0 +1,319 @@
+use crate::utils::secret::secret::{Secret, SecretSource};
+use crate::utils::totp::clock::{Clock, SystemClock};
+use crate::utils::totp::totp::{window_offsets, TotpError};
+use sm3::{Digest, Sm3};
+use sm4::cipher::{generic_array::GenericArray, BlockEncrypt, KeyInit};
+use sm4::Sm4;
+use std::fmt;
+use subtle::ConstantTimeEq;
+
+/// CN: GM/T 0021 规定的动态口令位数
+/// EN: Number of digits mandated by GM/T 0021
+pub const GM_DIGITS: usize = 6;
+
+/// CN: 标识ID的最小长度（128比特）
+/// EN: Minimum length of the identifier ID (128 bits)
+const MIN_ID_LENGTH: usize = 16;
+
+/// CN: SM4分组及密钥长度（128比特）
+/// EN: SM4 block and key length (128 bits)
+const SM4_BLOCK_SIZE: usize = 16;
+
+/// CN: GM/T 0021 动态口令算法枚举
+/// CN: 实现依据对标准正文的理解，尚未以标准附录的测试数据验证
+///
+/// EN: GM/T 0021 dynamic password algorithm enumeration
+/// EN: Implemented from a reading of the standard's text and not yet checked against the standard's appendix test data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GmAlgorithm {
+    /// CN: 基于SM3杂凑算法：S = SM3(K || ID)
+    /// EN: Based on the SM3 hash: S = SM3(K || ID)
+    #[default]
+    SM3,
+    /// CN: 基于SM4分组密码：以K为密钥（非128比特时取SM3(K)的前128比特）对ID做CBC加密，S为最后一个密文分组
+    /// EN: Based on the SM4 block cipher: ID is CBC-encrypted under K (the first 128 bits of SM3(K)
+    /// EN: when K is not 128 bits long), S being the last ciphertext block
+    SM4,
+}
+
+impl fmt::Display for GmAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GmAlgorithm::SM3 => write!(f, "SM3"),
+            GmAlgorithm::SM4 => write!(f, "SM4"),
+        }
+    }
+}
+
+/// CN: GM/T 0021 动态口令配置结构体
+/// EN: GM/T 0021 dynamic password configuration struct
+#[derive(Debug, Copy, Clone)]
+pub struct GmConfig {
+    /// CN: 使用的动态口令算法
+    /// EN: Dynamic password algorithm to use
+    pub algorithm: GmAlgorithm,
+    /// CN: 时间因子的口令变化周期Tc（1至60秒），为None时不使用时间因子
+    /// EN: Password change period Tc of the time factor (1 to 60 seconds), None disables the time factor
+    pub time_step: Option<u8>,
+    /// CN: 可选的自定义时间戳
+    /// EN: Optional custom timestamp
+    pub timestamp: Option<i64>,
+    /// CN: 时间因子的起始时间TID（Unix时间戳）
+    /// EN: Initial time TID of the time factor (Unix timestamp)
+    pub epoch: i64,
+    /// CN: 可选的事件因子C
+    /// EN: Optional event factor C
+    pub counter: Option<u32>,
+}
+
+/// CN: 为GmConfig实现默认值特征，默认使用SM3算法和60秒时间因子
+/// EN: Implement Default trait for GmConfig, using SM3 with a 60-second time factor
+impl Default for GmConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: GmAlgorithm::default(),
+            time_step: Some(60),
+            timestamp: None,
+            epoch: 0,
+            counter: None,
+        }
+    }
+}
+
+/// CN: 生成参照GM/T 0021的动态口令（实验性，尚未以标准附录的测试数据验证）
+/// CN: 参数:
+/// CN: - secret: 种子密钥K（Base32字符串或Secret，至少128比特）
+/// CN: - challenge: 可选的挑战因子Q（至少4字节）
+/// CN: - config: 动态口令配置选项
+/// CN: 返回:
+/// CN: - Ok(String): 成功生成的6位动态口令
+/// CN: - Err(TotpError): 生成过程中的错误
+///
+/// EN: Generate a dynamic password modelled on GM/T 0021 (experimental, not yet checked against the standard's appendix test data)
+/// EN: Parameters:
+/// EN: - secret: Seed key K (Base32 string or Secret, at least 128 bits)
+/// EN: - challenge: Optional challenge factor Q (at least 4 bytes)
+/// EN: - config: Dynamic password configuration options
+/// EN: Returns:
+/// EN: - Ok(String): Successfully generated 6-digit dynamic password
+/// EN: - Err(TotpError): Errors during generation
+pub fn generate_gm_code<S: SecretSource + ?Sized>(
+    secret: &S,
+    challenge: Option<&str>,
+    config: Option<GmConfig>,
+) -> Result<String, TotpError> {
+    generate_gm_code_with_clock(secret, challenge, config, &SystemClock)
+}
+
+/// CN: 使用指定时间源生成GM/T 0021动态口令，配置中的自定义时间戳优先于时间源
+/// EN: Generate a GM/T 0021 dynamic password using the given time source, a custom timestamp in the configuration taking precedence over it
+pub fn generate_gm_code_with_clock<S: SecretSource + ?Sized, C: Clock + ?Sized>(
+    secret: &S,
+    challenge: Option<&str>,
+    config: Option<GmConfig>,
+    clock: &C,
+) -> Result<String, TotpError> {
+    let config = config.unwrap_or_default();
+    let secret = decode_gm_secret(secret)?;
+    let time_factor = time_factor(&config, clock)?;
+    compute_gm_code(secret.as_bytes(), time_factor, challenge, &config)
+}
+
+/// CN: 校验GM/T 0021动态口令，允许时间因子前后若干个周期的偏差
+/// CN: 参数:
+/// CN: - secret: 种子密钥K（Base32字符串或Secret）
+/// CN: - code: 待校验的动态口令
+/// CN: - challenge: 可选的挑战因子Q
+/// CN: - config: 动态口令配置选项
+/// CN: - look_behind: 向前回溯的周期数（仅在使用时间因子时生效）
+/// CN: - look_ahead: 向后预读的周期数（仅在使用时间因子时生效）
+/// CN: 返回:
+/// CN: - Ok(i64): 匹配成功的时间因子偏移量
+/// CN: - Err(TotpError): 口令不匹配或校验过程中的错误
+///
+/// EN: Verify GM/T 0021 dynamic password, tolerating a skew of several periods of the time factor
+/// EN: Parameters:
+/// EN: - secret: Seed key K (Base32 string or Secret)
+/// EN: - code: Password submitted for verification
+/// EN: - challenge: Optional challenge factor Q
+/// EN: - config: Dynamic password configuration options
+/// EN: - look_behind: Number of past periods to accept (only with the time factor)
+/// EN: - look_ahead: Number of future periods to accept (only with the time factor)
+/// EN: Returns:
+/// EN: - Ok(i64): Offset of the time factor that matched
+/// EN: - Err(TotpError): Password mismatch or errors during verification
+pub fn verify_gm_code<S: SecretSource + ?Sized>(
+    secret: &S,
+    code: &str,
+    challenge: Option<&str>,
+    config: Option<GmConfig>,
+    look_behind: u32,
+    look_ahead: u32,
+) -> Result<i64, TotpError> {
+    verify_gm_code_with_clock(
+        secret,
+        code,
+        challenge,
+        config,
+        look_behind,
+        look_ahead,
+        &SystemClock,
+    )
+}
+
+/// CN: 使用指定时间源校验GM/T 0021动态口令，配置中的自定义时间戳优先于时间源
+/// EN: Verify a GM/T 0021 dynamic password using the given time source, a custom timestamp in the configuration taking precedence over it
+pub fn verify_gm_code_with_clock<S: SecretSource + ?Sized, C: Clock + ?Sized>(
+    secret: &S,
+    code: &str,
+    challenge: Option<&str>,
+    config: Option<GmConfig>,
+    look_behind: u32,
+    look_ahead: u32,
+    clock: &C,
+) -> Result<i64, TotpError> {
+    let config = config.unwrap_or_default();
+    let secret = decode_gm_secret(secret)?;
+    let time_factor = time_factor(&config, clock)?;
+    let (look_behind, look_ahead) = match time_factor {
+        Some(_) => (look_behind, look_ahead),
+        None => (0, 0),
+    };
+
+    for offset in window_offsets(look_behind, look_ahead) {
+        // CN: 跳过早于起始时间的时间因子
+        // EN: Skip time factors before the initial time
+        let time_factor = match time_factor {
+            Some(t) => match t.checked_add(offset) {
+                Some(t) if t >= 0 => Some(t),
+                _ => continue,
+            },
+            None => None,
+        };
+        let expected = compute_gm_code(secret.as_bytes(), time_factor, challenge, &config)?;
+        if bool::from(expected.as_bytes().ct_eq(code.as_bytes())) {
+            return Ok(offset);
+        }
+    }
+    Err(TotpError::CodeMismatch)
+}
+
+/// CN: 解码种子密钥并校验其长度不少于128比特
+/// EN: Decode the seed key and validate it is at least 128 bits long
+fn decode_gm_secret<S: SecretSource + ?Sized>(secret: &S) -> Result<Secret, TotpError> {
+    let secret = secret.to_secret()?;
+    if secret.len() < 16 {
+        return Err(TotpError::InvalidKeyLength);
+    }
+    Ok(secret)
+}
+
+/// CN: 计算时间因子 T = (T0 - TID) / Tc
+/// EN: Compute the time factor T = (T0 - TID) / Tc
+fn time_factor<C: Clock + ?Sized>(config: &GmConfig, clock: &C) -> Result<Option<i64>, TotpError> {
+    let Some(time_step) = config.time_step else {
+        return Ok(None);
+    };
+    if !(1..=60).contains(&time_step) {
+        return Err(TotpError::InvalidFactor);
+    }
+    let timestamp = config
+        .timestamp
+        .unwrap_or_else(|| clock.now().as_secs() as i64);
+    let elapsed = timestamp
+        .checked_sub(config.epoch)
+        .filter(|elapsed| *elapsed >= 0)
+        .ok_or(TotpError::InvalidFactor)?;
+    Ok(Some(elapsed / time_step as i64))
+}
+
+/// CN: 按 ID = T || C || Q 构造标识，不足128比特时右侧补0
+/// EN: Build the identifier ID = T || C || Q, right-padded with zeros to 128 bits
+fn build_id(
+    time_factor: Option<i64>,
+    counter: Option<u32>,
+    challenge: Option<&str>,
+) -> Result<Vec<u8>, TotpError> {
+    if time_factor.is_none() && counter.is_none() && challenge.is_none() {
+        return Err(TotpError::InvalidFactor);
+    }
+    let mut id = Vec::with_capacity(MIN_ID_LENGTH);
+    if let Some(t) = time_factor {
+        id.extend_from_slice(&(t as u64).to_be_bytes());
+    }
+    if let Some(c) = counter {
+        id.extend_from_slice(&c.to_be_bytes());
+    }
+    if let Some(q) = challenge {
+        if q.len() < 4 {
+            return Err(TotpError::InvalidFactor);
+        }
+        id.extend_from_slice(q.as_bytes());
+    }
+    if id.len() < MIN_ID_LENGTH {
+        id.resize(MIN_ID_LENGTH, 0);
+    }
+    Ok(id)
+}
+
+/// CN: 根据种子密钥和各因子计算动态口令
+/// EN: Compute the dynamic password from the seed key and factors
+fn compute_gm_code(
+    secret_bytes: &[u8],
+    time_factor: Option<i64>,
+    challenge: Option<&str>,
+    config: &GmConfig,
+) -> Result<String, TotpError> {
+    let id = build_id(time_factor, config.counter, challenge)?;
+    let s = match config.algorithm {
+        GmAlgorithm::SM3 => {
+            let mut hasher = Sm3::new();
+            hasher.update(secret_bytes);
+            hasher.update(&id);
+            hasher.finalize().to_vec()
+        }
+        GmAlgorithm::SM4 => sm4_cbc_mac(secret_bytes, &id),
+    };
+    Ok(truncate(&s))
+}
+
+/// CN: 使用SM4以全零初始向量对ID做CBC加密，返回最后一个密文分组
+/// CN: 密钥压缩、初始向量与补0方式均为对标准正文的理解，尚未以标准附录的测试数据验证
+///
+/// EN: CBC-encrypt ID with SM4 and an all-zero IV, returning the last ciphertext block
+/// EN: The key compression, IV and zero padding follow a reading of the standard's text and are not yet checked against its appendix test data
+fn sm4_cbc_mac(secret_bytes: &[u8], id: &[u8]) -> Vec<u8> {
+    // CN: 种子密钥不是128比特时，使用SM3压缩为SM4密钥
+    // EN: Compress the seed key into an SM4 key with SM3 when it is not 128 bits long
+    let key = if secret_bytes.len() == SM4_BLOCK_SIZE {
+        GenericArray::clone_from_slice(secret_bytes)
+    } else {
+        GenericArray::clone_from_slice(&Sm3::digest(secret_bytes)[..SM4_BLOCK_SIZE])
+    };
+    let cipher = Sm4::new(&key);
+
+    let mut state = GenericArray::default();
+    for block in id.chunks(SM4_BLOCK_SIZE) {
+        // CN: 末尾不足一个分组的部分右侧补0
+        // EN: A trailing partial block is right-padded with zeros
+        for (byte, input) in state.iter_mut().zip(block) {
+            *byte ^= input;
+        }
+        cipher.encrypt_block(&mut state);
+    }
+    state.to_vec()
+}
+
+/// CN: GM/T 0021 截位：将S按32比特分组求和（模2^32），再对10^6取模
+/// EN: GM/T 0021 truncation: sum S in 32-bit words (mod 2^32), then reduce modulo 10^6
+fn truncate(s: &[u8]) -> String {
+    let od = s
+        .chunks_exact(4)
+        .map(|word| u32::from_be_bytes([word[0], word[1], word[2], word[3]]))
+        .fold(0u32, |sum, word| sum.wrapping_add(word));
+    format!(
+        "{:0digits$}",
+        od % 10u32.pow(GM_DIGITS as u32),
+        digits = GM_DIGITS
+    )
+}
@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod gm;
+pub use gm::{
+    generate_gm_code, generate_gm_code_with_clock, verify_gm_code, verify_gm_code_with_clock,
+    GmAlgorithm, GmConfig, GM_DIGITS,
+};
@@ -0,0 +1,376 @@
+#[cfg(feature = "std")]
+use crate::utils::secret::secret::SecretSource;
+use crate::utils::totp::totp::{check_digits, HashAlgorithm, MacVisitor, TotpError};
+use core::fmt;
+use hmac::digest::core_api::BlockSizeUser;
+use hmac::digest::{Digest, KeyInit, Output};
+use hmac::{Mac, SimpleHmac};
+use subtle::ConstantTimeEq;
+
+/// CN: 动态截取所需的最小摘要长度（字节）
+/// EN: Minimum digest length in bytes required by dynamic truncation
+pub const MIN_DIGEST_SIZE: usize = 20;
+
+/// CN: 验证码的最大位数
+/// EN: Maximum number of code digits
+pub const MAX_DIGITS: usize = 10;
+
+/// CN: 验证码数值及其位数，可不经堆分配写入调用方提供的缓冲区
+/// EN: Code value with its number of digits, writable into a caller-provided buffer without heap allocation
+///
+/// ```
+/// use totp_sm_rs::utils::hotp::hotp::{hotp_value, MAX_DIGITS};
+/// use totp_sm_rs::utils::totp::totp::HashAlgorithm;
+///
+/// let value = hotp_value(b"12345678901234567890", 7, 6, HashAlgorithm::SHA1).unwrap();
+/// assert_eq!(value.value(), 162583);
+/// let mut buf = [0u8; MAX_DIGITS];
+/// assert_eq!(value.write_to(&mut buf).unwrap(), "162583");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OtpValue {
+    /// CN: 验证码数值
+    /// EN: Code value
+    value: u32,
+    /// CN: 验证码位数
+    /// EN: Number of code digits
+    digits: u8,
+}
+
+impl OtpValue {
+    pub(crate) fn new(value: u32, digits: u8) -> Self {
+        Self { value, digits }
+    }
+
+    /// CN: 获取验证码数值（不含前导零）
+    /// EN: Get the code value (without leading zeros)
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// CN: 获取验证码位数
+    /// EN: Get the number of code digits
+    pub fn digits(&self) -> u8 {
+        self.digits
+    }
+
+    /// CN: 将补足前导零的验证码写入缓冲区开头，返回写入部分的字符串视图
+    /// CN: 缓冲区长度小于位数时返回BufferTooSmall
+    ///
+    /// EN: Write the zero-padded code to the start of the buffer, returning a string view of the written part
+    /// EN: Returns BufferTooSmall when the buffer is shorter than the number of digits
+    pub fn write_to<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, TotpError> {
+        let digits = buf
+            .get_mut(..self.digits as usize)
+            .ok_or(TotpError::BufferTooSmall)?;
+        let mut value = self.value;
+        for digit in digits.iter_mut().rev() {
+            *digit = b'0' + (value % 10) as u8;
+            value /= 10;
+        }
+        Ok(core::str::from_utf8(digits).unwrap_or_default())
+    }
+
+    /// CN: 以常量时间比较验证码字符串
+    /// EN: Compare against a code string in constant time
+    pub fn matches(&self, code: &str) -> bool {
+        let mut buf = [0u8; MAX_DIGITS];
+        let expected = self.write_to(&mut buf).unwrap_or_default();
+        bool::from(expected.as_bytes().ct_eq(code.as_bytes()))
+    }
+}
+
+/// CN: 以补足前导零的形式显示验证码
+/// EN: Display the code zero-padded
+impl fmt::Display for OtpValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:0digits$}", self.value, digits = self.digits as usize)
+    }
+}
+
+/// CN: 生成HOTP验证码（RFC 4226）
+/// CN: 参数:
+/// CN: - secret: 密钥（Base32字符串或Secret）
+/// CN: - counter: 事件计数器
+/// CN: - digits: 验证码位数（4至10位）
+/// CN: - hash_algorithm: 使用的哈希算法
+/// CN: 返回:
+/// CN: - Ok(String): 成功生成的HOTP验证码
+/// CN: - Err(TotpError): 生成过程中的错误
+///
+/// EN: Generate HOTP verification code (RFC 4226)
+/// EN: Parameters:
+/// EN: - secret: Secret key (Base32 string or Secret)
+/// EN: - counter: Event counter
+/// EN: - digits: Number of digits in the code (4 to 10)
+/// EN: - hash_algorithm: Hash algorithm to use
+/// EN: Returns:
+/// EN: - Ok(String): Successfully generated HOTP code
+/// EN: - Err(TotpError): Errors during generation
+#[cfg(feature = "std")]
+pub fn generate_hotp_code<S: SecretSource + ?Sized>(
+    secret: &S,
+    counter: u64,
+    digits: u8,
+    hash_algorithm: HashAlgorithm,
+) -> Result<String, TotpError> {
+    check_digits(digits)?;
+    let secret = secret.to_secret()?;
+    compute_hotp(secret.as_bytes(), counter, digits, hash_algorithm)
+}
+
+/// CN: 校验HOTP验证码，允许计数器向后预读若干步（RFC 4226 §7.4）
+/// CN: 参数:
+/// CN: - secret: 密钥（Base32字符串或Secret）
+/// CN: - code: 待校验的验证码
+/// CN: - counter: 服务端保存的当前计数器
+/// CN: - digits: 验证码位数（4至10位）
+/// CN: - hash_algorithm: 使用的哈希算法
+/// CN: - look_ahead: 向后预读的计数器步数
+/// CN: 返回:
+/// CN: - Ok(u64): 校验成功后需要保存的新计数器（匹配计数器加1）
+/// CN: - Err(TotpError): 验证码不匹配、匹配计数器为u64::MAX（CounterExhausted）或校验过程中的错误
+///
+/// EN: Verify HOTP verification code, allowing the counter to look ahead several steps (RFC 4226 §7.4)
+/// EN: Parameters:
+/// EN: - secret: Secret key (Base32 string or Secret)
+/// EN: - code: Code submitted for verification
+/// EN: - counter: Current counter stored on the server
+/// EN: - digits: Number of digits in the code (4 to 10)
+/// EN: - hash_algorithm: Hash algorithm to use
+/// EN: - look_ahead: Number of counter steps to look ahead
+/// EN: Returns:
+/// EN: - Ok(u64): New counter to persist after success (matched counter plus one)
+/// EN: - Err(TotpError): Code mismatch, a match at u64::MAX (CounterExhausted) or errors during verification
+#[cfg(feature = "std")]
+pub fn verify_hotp<S: SecretSource + ?Sized>(
+    secret: &S,
+    code: &str,
+    counter: u64,
+    digits: u8,
+    hash_algorithm: HashAlgorithm,
+    look_ahead: u32,
+) -> Result<u64, TotpError> {
+    check_digits(digits)?;
+    let secret = secret.to_secret()?;
+    for step in 0..=look_ahead as u64 {
+        let Some(candidate) = counter.checked_add(step) else {
+            break;
+        };
+        let expected = compute_hotp_value(secret.as_bytes(), candidate, digits, hash_algorithm)?;
+        if expected.matches(code) {
+            // CN: 计数器不能回绕到0，否则所有旧验证码将可被重放
+            // EN: The counter must not wrap to 0, which would reopen every past code for replay
+            return candidate.checked_add(1).ok_or(TotpError::CounterExhausted);
+        }
+    }
+    Err(TotpError::CodeMismatch)
+}
+
+/// CN: 由原始密钥字节计算HOTP验证码数值，整个计算过程不进行堆分配
+/// CN: 参数:
+/// CN: - secret_bytes: 已解码的密钥字节
+/// CN: - counter: 事件计数器
+/// CN: - digits: 验证码位数（4至10位）
+/// CN: - hash_algorithm: 使用的哈希算法
+/// CN: 返回:
+/// CN: - Ok(OtpValue): 验证码数值及位数
+/// CN: - Err(TotpError): 位数无效
+///
+/// EN: Compute the HOTP code value from raw secret bytes, without any heap allocation
+/// EN: Parameters:
+/// EN: - secret_bytes: Decoded secret bytes
+/// EN: - counter: Event counter
+/// EN: - digits: Number of digits in the code (4 to 10)
+/// EN: - hash_algorithm: Hash algorithm to use
+/// EN: Returns:
+/// EN: - Ok(OtpValue): Code value and number of digits
+/// EN: - Err(TotpError): Invalid number of digits
+pub fn hotp_value(
+    secret_bytes: &[u8],
+    counter: u64,
+    digits: u8,
+    hash_algorithm: HashAlgorithm,
+) -> Result<OtpValue, TotpError> {
+    check_digits(digits)?;
+    compute_hotp_value(secret_bytes, counter, digits, hash_algorithm)
+}
+
+/// CN: 由原始密钥字节计算HOTP验证码并写入缓冲区，返回写入部分的字符串视图，不进行堆分配
+/// EN: Compute the HOTP code from raw secret bytes into the buffer, returning a string view of the written part, without heap allocation
+pub fn write_hotp_code<'a>(
+    secret_bytes: &[u8],
+    counter: u64,
+    digits: u8,
+    hash_algorithm: HashAlgorithm,
+    buf: &'a mut [u8],
+) -> Result<&'a str, TotpError> {
+    hotp_value(secret_bytes, counter, digits, hash_algorithm)?.write_to(buf)
+}
+
+/// CN: 使用任意摘要算法（实现Digest与BlockSizeUser，如厂商自定义哈希）计算HOTP验证码数值，不进行堆分配
+/// CN: 参数:
+/// CN: - secret_bytes: 已解码的密钥字节
+/// CN: - counter: 事件计数器
+/// CN: - digits: 验证码位数（4至10位）
+/// CN: 返回:
+/// CN: - Ok(OtpValue): 验证码数值及位数
+/// CN: - Err(TotpError): 位数无效，或摘要长度不足20字节
+///
+/// EN: Compute the HOTP code value with any digest (implementing Digest and BlockSizeUser, e.g. a custom vendor hash), without heap allocation
+/// EN: Parameters:
+/// EN: - secret_bytes: Decoded secret bytes
+/// EN: - counter: Event counter
+/// EN: - digits: Number of digits in the code (4 to 10)
+/// EN: Returns:
+/// EN: - Ok(OtpValue): Code value and number of digits
+/// EN: - Err(TotpError): Invalid number of digits, or a digest shorter than 20 bytes
+///
+/// ```
+/// use sha2::Sha256;
+/// use totp_sm_rs::utils::hotp::hotp::{hotp_value, hotp_value_with_digest};
+/// use totp_sm_rs::utils::totp::totp::HashAlgorithm;
+///
+/// let secret = b"12345678901234567890";
+/// assert_eq!(
+///     hotp_value_with_digest::<Sha256>(secret, 7, 6).unwrap(),
+///     hotp_value(secret, 7, 6, HashAlgorithm::SHA256).unwrap()
+/// );
+/// ```
+pub fn hotp_value_with_digest<D: Digest + BlockSizeUser>(
+    secret_bytes: &[u8],
+    counter: u64,
+    digits: u8,
+) -> Result<OtpValue, TotpError> {
+    check_digits(digits)?;
+    digest_value::<D>(secret_bytes, counter, digits)
+}
+
+/// CN: 根据密钥和计数器计算HMAC并按RFC 4226动态截取得到验证码
+/// EN: Compute the HMAC over the counter and derive the code by RFC 4226 dynamic truncation
+#[cfg(feature = "std")]
+pub(crate) fn compute_hotp(
+    secret_bytes: &[u8],
+    counter: u64,
+    digits: u8,
+    hash_algorithm: HashAlgorithm,
+) -> Result<String, TotpError> {
+    Ok(compute_hotp_value(secret_bytes, counter, digits, hash_algorithm)?.to_string())
+}
+
+/// CN: 根据密钥和计数器计算验证码数值，按哈希算法选择对应的HMAC实现，HMAC结果保存在栈上
+/// EN: Compute the code value over the counter with the HMAC selected by the hash algorithm, keeping the HMAC result on the stack
+pub(crate) fn compute_hotp_value(
+    secret_bytes: &[u8],
+    counter: u64,
+    digits: u8,
+    hash_algorithm: HashAlgorithm,
+) -> Result<OtpValue, TotpError> {
+    struct HotpValue<'a> {
+        secret_bytes: &'a [u8],
+        counter: u64,
+        digits: u8,
+    }
+    impl MacVisitor for HotpValue<'_> {
+        type Output = Result<OtpValue, TotpError>;
+        fn visit<M: Mac + KeyInit + Clone>(self) -> Self::Output {
+            mac_value::<M>(self.secret_bytes, self.counter, self.digits)
+        }
+    }
+    hash_algorithm.visit_mac(HotpValue {
+        secret_bytes,
+        counter,
+        digits,
+    })
+}
+
+/// CN: 使用给定摘要算法计算计数器的HMAC并动态截取得到验证码数值，摘要长度不足20字节时返回UnsupportedDigest
+/// CN: 任意摘要只能使用 `SimpleHmac`，内置算法经由 `compute_hotp_value` 使用各自的HMAC实现
+///
+/// EN: Compute the counter's HMAC with the given digest and dynamically truncate it to the code value,
+/// EN: returning UnsupportedDigest for digests shorter than 20 bytes
+/// EN: An arbitrary digest can only use `SimpleHmac`; built-in algorithms go through `compute_hotp_value` and their own HMAC
+pub(crate) fn digest_value<D: Digest + BlockSizeUser>(
+    secret_bytes: &[u8],
+    counter: u64,
+    digits: u8,
+) -> Result<OtpValue, TotpError> {
+    if <D as Digest>::output_size() < MIN_DIGEST_SIZE {
+        return Err(TotpError::UnsupportedDigest);
+    }
+    mac_value::<SimpleHmac<D>>(secret_bytes, counter, digits)
+}
+
+/// CN: 使用给定HMAC实现计算计数器的HMAC并动态截取得到验证码数值
+/// EN: Compute the counter's HMAC with the given HMAC implementation and dynamically truncate it to the code value
+fn mac_value<M: Mac + KeyInit>(
+    secret_bytes: &[u8],
+    counter: u64,
+    digits: u8,
+) -> Result<OtpValue, TotpError> {
+    // CN: 将计数器转换为大端字节数组
+    // EN: Convert counter to big-endian byte array
+    let message = counter.to_be_bytes();
+    let value = truncate_value(&mac_output::<M>(secret_bytes, &message)?, digits);
+    Ok(OtpValue::new(value, digits))
+}
+
+/// CN: 使用给定HMAC实现计算消息的HMAC
+/// EN: Compute the HMAC of a message with the given HMAC implementation
+fn mac_output<M: Mac + KeyInit>(
+    secret_bytes: &[u8],
+    message: &[u8],
+) -> Result<Output<M>, TotpError> {
+    let mut mac =
+        <M as Mac>::new_from_slice(secret_bytes).map_err(|_| TotpError::InvalidKeyLength)?;
+    mac.update(message);
+    Ok(mac.finalize().into_bytes())
+}
+
+/// CN: 使用选择的哈希算法计算消息的HMAC
+/// EN: Compute the HMAC of a message with the selected hash algorithm
+#[cfg(feature = "std")]
+pub(crate) fn compute_hmac(
+    secret_bytes: &[u8],
+    message: &[u8],
+    hash_algorithm: HashAlgorithm,
+) -> Result<Vec<u8>, TotpError> {
+    struct HmacBytes<'a> {
+        secret_bytes: &'a [u8],
+        message: &'a [u8],
+    }
+    impl MacVisitor for HmacBytes<'_> {
+        type Output = Result<Vec<u8>, TotpError>;
+        fn visit<M: Mac + KeyInit + Clone>(self) -> Self::Output {
+            Ok(mac_output::<M>(self.secret_bytes, self.message)?.to_vec())
+        }
+    }
+    hash_algorithm.visit_mac(HmacBytes {
+        secret_bytes,
+        message,
+    })
+}
+
+/// CN: 动态截取HMAC结果并格式化为指定位数的验证码（RFC 4226 §5.3）
+/// EN: Dynamically truncate the HMAC result and format it with the given digits (RFC 4226 §5.3)
+#[cfg(feature = "std")]
+pub(crate) fn truncate(hmac_result: &[u8], digits: u8) -> String {
+    let code = truncate_value(hmac_result, digits);
+    format!("{:0digits$}", code, digits = digits as usize)
+}
+
+/// CN: 动态截取HMAC结果，返回取模后的验证码数值
+/// EN: Dynamically truncate the HMAC result, returning the code value after the modulo
+pub(crate) fn truncate_value(hmac_result: &[u8], digits: u8) -> u32 {
+    // CN: 动态截取偏移量
+    // EN: Dynamic truncation offset
+    let offset = (hmac_result[hmac_result.len() - 1] & 0xf) as usize;
+
+    let code = ((hmac_result[offset] as u64 & 0x7f) << 24
+        | (hmac_result[offset + 1] as u64 & 0xff) << 16
+        | (hmac_result[offset + 2] as u64 & 0xff) << 8
+        | (hmac_result[offset + 3] as u64 & 0xff))
+        % 10u64.pow(digits as u32);
+
+    code as u32
+}
@@ -0,0 +1,5 @@
+#[allow(clippy::module_inception)]
+pub mod hotp;
+#[cfg(feature = "std")]
+pub use hotp::{generate_hotp_code, verify_hotp};
+pub use hotp::{hotp_value, hotp_value_with_digest, write_hotp_code, OtpValue};
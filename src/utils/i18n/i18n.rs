@@ -11,6 +11,27 @@ struct I18nConfig {
     /// CN: TOTP相关消息
     /// EN: TOTP related messages
     totp: TotpMessages,
+    /// CN: OCRA相关消息
+    /// EN: OCRA related messages
+    ocra: OcraMessages,
+    /// CN: otpauth URI相关消息
+    /// EN: otpauth URI related messages
+    uri: UriMessages,
+    /// CN: 二维码相关消息
+    /// EN: QR code related messages
+    qr: QrMessages,
+    /// CN: 迁移数据相关消息
+    /// EN: Migration data related messages
+    migration: MigrationMessages,
+    /// CN: 二维码图片导入相关消息
+    /// EN: QR code image import related messages
+    scan: ScanMessages,
+    /// CN: 密钥相关消息
+    /// EN: Secret related messages
+    secret: SecretMessages,
+    /// CN: 哈希算法解析相关消息
+    /// EN: Hash algorithm parsing related messages
+    algorithm: AlgorithmMessages,
 }
 
 /// CN: TOTP消息结构体
@@ -44,6 +65,222 @@ struct TotpErrors {
     /// CN: 时间戳无效错误消息
     /// EN: Invalid timestamp error message
     invalid_timestamp: String,
+    /// CN: 验证码不匹配错误消息
+    /// EN: Code mismatch error message
+    code_mismatch: String,
+    /// CN: 验证码重放错误消息
+    /// EN: Replayed code error message
+    replayed_code: String,
+    /// CN: 动态口令因子无效错误消息
+    /// EN: Invalid dynamic password factor error message
+    invalid_factor: String,
+    /// CN: 输出缓冲区过小错误消息
+    /// EN: Output buffer too small error message
+    buffer_too_small: String,
+    /// CN: 摘要长度不足错误消息
+    /// EN: Digest too short error message
+    unsupported_digest: String,
+    /// CN: 计数器耗尽错误消息
+    /// EN: Counter exhausted error message
+    counter_exhausted: String,
+}
+
+/// CN: OCRA消息结构体
+/// EN: OCRA messages struct
+#[derive(Debug, Deserialize)]
+struct OcraMessages {
+    /// CN: 错误消息集合
+    /// EN: Error messages collection
+    errors: OcraErrors,
+}
+
+/// CN: OCRA错误消息结构体
+/// EN: OCRA error messages struct
+#[derive(Debug, Deserialize)]
+struct OcraErrors {
+    /// CN: 套件无效错误消息
+    /// EN: Invalid suite error message
+    invalid_suite: String,
+    /// CN: 输入缺失错误消息
+    /// EN: Missing input error message
+    missing_input: String,
+    /// CN: 输入无效错误消息
+    /// EN: Invalid input error message
+    invalid_input: String,
+}
+
+/// CN: otpauth URI消息结构体
+/// EN: otpauth URI messages struct
+#[derive(Debug, Deserialize)]
+struct UriMessages {
+    /// CN: 错误消息集合
+    /// EN: Error messages collection
+    errors: UriErrors,
+}
+
+/// CN: otpauth URI错误消息结构体
+/// EN: otpauth URI error messages struct
+#[derive(Debug, Deserialize)]
+struct UriErrors {
+    /// CN: 协议无效错误消息
+    /// EN: Invalid scheme error message
+    invalid_scheme: String,
+    /// CN: 口令类型无效错误消息
+    /// EN: Invalid OTP type error message
+    invalid_type: String,
+    /// CN: 标签无效错误消息
+    /// EN: Invalid label error message
+    invalid_label: String,
+    /// CN: 参数缺失错误消息
+    /// EN: Missing parameter error message
+    missing_parameter: String,
+    /// CN: 参数无效错误消息
+    /// EN: Invalid parameter error message
+    invalid_parameter: String,
+    /// CN: 未知参数错误消息
+    /// EN: Unknown parameter error message
+    unknown_parameter: String,
+    /// CN: 重复参数错误消息
+    /// EN: Duplicate parameter error message
+    duplicate_parameter: String,
+    /// CN: 发行方不一致错误消息
+    /// EN: Issuer mismatch error message
+    issuer_mismatch: String,
+}
+
+/// CN: 二维码消息结构体
+/// EN: QR code messages struct
+#[derive(Debug, Deserialize)]
+struct QrMessages {
+    /// CN: 错误消息集合
+    /// EN: Error messages collection
+    errors: QrErrors,
+}
+
+/// CN: 二维码错误消息结构体
+/// EN: QR code error messages struct
+#[derive(Debug, Deserialize)]
+struct QrErrors {
+    /// CN: 编码失败错误消息
+    /// EN: Encoding failed error message
+    encode: String,
+    /// CN: 模块尺寸无效错误消息
+    /// EN: Invalid module size error message
+    invalid_module_size: String,
+    /// CN: PNG编码失败错误消息
+    /// EN: PNG encoding failed error message
+    png: String,
+}
+
+/// CN: 迁移数据消息结构体
+/// EN: Migration data messages struct
+#[derive(Debug, Deserialize)]
+struct MigrationMessages {
+    /// CN: 错误消息集合
+    /// EN: Error messages collection
+    errors: MigrationErrors,
+}
+
+/// CN: 迁移数据错误消息结构体
+/// EN: Migration data error messages struct
+#[derive(Debug, Deserialize)]
+struct MigrationErrors {
+    /// CN: 迁移URI无效错误消息
+    /// EN: Invalid migration URI error message
+    invalid_uri: String,
+    /// CN: 迁移数据无效错误消息
+    /// EN: Invalid migration data error message
+    invalid_data: String,
+    /// CN: 不支持的算法错误消息
+    /// EN: Unsupported algorithm error message
+    unsupported_algorithm: String,
+    /// CN: 不支持的位数错误消息
+    /// EN: Unsupported digits error message
+    unsupported_digits: String,
+    /// CN: 不支持的时间步长错误消息
+    /// EN: Unsupported period error message
+    unsupported_period: String,
+    /// CN: 分批数据无效错误消息
+    /// EN: Invalid batch error message
+    invalid_batch: String,
+}
+
+/// CN: 二维码图片导入消息结构体
+/// EN: QR code image import messages struct
+#[derive(Debug, Deserialize)]
+struct ScanMessages {
+    /// CN: 错误消息集合
+    /// EN: Error messages collection
+    errors: ScanErrors,
+}
+
+/// CN: 二维码图片导入错误消息结构体
+/// EN: QR code image import error messages struct
+#[derive(Debug, Deserialize)]
+struct ScanErrors {
+    /// CN: 图片解码失败错误消息
+    /// EN: Image decoding failed error message
+    image: String,
+    /// CN: 未找到otpauth二维码错误消息
+    /// EN: No otpauth QR code found error message
+    no_otp_code: String,
+}
+
+/// CN: 密钥消息结构体
+/// EN: Secret messages struct
+#[derive(Debug, Deserialize)]
+struct SecretMessages {
+    /// CN: 错误消息集合
+    /// EN: Error messages collection
+    errors: SecretErrors,
+}
+
+/// CN: 密钥错误消息结构体
+/// EN: Secret error messages struct
+#[derive(Debug, Deserialize)]
+struct SecretErrors {
+    /// CN: 随机数生成器不可用错误消息
+    /// EN: Random number generator unavailable error message
+    random: String,
+    /// CN: 密钥长度无效错误消息
+    /// EN: Invalid secret length error message
+    invalid_length: String,
+    /// CN: 密钥编码无效错误消息
+    /// EN: Invalid secret encoding error message
+    invalid_encoding: String,
+    /// CN: 密钥含非法字符错误消息
+    /// EN: Invalid character in secret error message
+    invalid_character: String,
+}
+
+/// CN: 哈希算法解析消息结构体
+/// EN: Hash algorithm parsing messages struct
+#[derive(Debug, Deserialize)]
+struct AlgorithmMessages {
+    /// CN: 错误消息集合
+    /// EN: Error messages collection
+    errors: AlgorithmErrors,
+}
+
+/// CN: 哈希算法解析错误消息结构体
+/// EN: Hash algorithm parsing error messages struct
+#[derive(Debug, Deserialize)]
+struct AlgorithmErrors {
+    /// CN: 算法为空错误消息
+    /// EN: Empty algorithm error message
+    empty: String,
+    /// CN: 未知算法名称错误消息
+    /// EN: Unknown algorithm name error message
+    unknown_name: String,
+    /// CN: 未知OID错误消息
+    /// EN: Unknown OID error message
+    unknown_oid: String,
+    /// CN: 不支持的算法错误消息
+    /// EN: Unsupported algorithm error message
+    unsupported: String,
+    /// CN: 特性未启用错误消息
+    /// EN: Feature not enabled error message
+    feature_disabled: String,
 }
 
 /// CN: 国际化处理结构体
@@ -57,6 +294,12 @@ pub struct I18n {
     current_locale: String,
 }
 
+impl Default for I18n {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl I18n {
     pub fn new() -> Self {
         let mut i18n = I18n {
@@ -70,13 +313,14 @@ impl I18n {
 
     fn load_default_locales(&mut self) {
         let locales_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/utils/i18n/locales");
-        for entry in fs::read_dir(locales_dir).expect("Failed to read locales directory") {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "yml") {
-                    let locale = path.file_stem().unwrap().to_str().unwrap();
-                    self.load_locale(locale, &path);
-                }
+        for entry in fs::read_dir(locales_dir)
+            .expect("Failed to read locales directory")
+            .flatten()
+        {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "yml") {
+                let locale = path.file_stem().unwrap().to_str().unwrap();
+                self.load_locale(locale, &path);
             }
         }
     }
@@ -111,6 +355,49 @@ impl I18n {
             "totp.errors.invalid_digits" => Some(&config.totp.errors.invalid_digits),
             "totp.errors.invalid_time_step" => Some(&config.totp.errors.invalid_time_step),
             "totp.errors.invalid_timestamp" => Some(&config.totp.errors.invalid_timestamp),
+            "totp.errors.code_mismatch" => Some(&config.totp.errors.code_mismatch),
+            "totp.errors.replayed_code" => Some(&config.totp.errors.replayed_code),
+            "totp.errors.buffer_too_small" => Some(&config.totp.errors.buffer_too_small),
+            "totp.errors.unsupported_digest" => Some(&config.totp.errors.unsupported_digest),
+            "totp.errors.counter_exhausted" => Some(&config.totp.errors.counter_exhausted),
+            "totp.errors.invalid_factor" => Some(&config.totp.errors.invalid_factor),
+            "ocra.errors.invalid_suite" => Some(&config.ocra.errors.invalid_suite),
+            "ocra.errors.missing_input" => Some(&config.ocra.errors.missing_input),
+            "ocra.errors.invalid_input" => Some(&config.ocra.errors.invalid_input),
+            "uri.errors.invalid_scheme" => Some(&config.uri.errors.invalid_scheme),
+            "uri.errors.invalid_type" => Some(&config.uri.errors.invalid_type),
+            "uri.errors.invalid_label" => Some(&config.uri.errors.invalid_label),
+            "uri.errors.missing_parameter" => Some(&config.uri.errors.missing_parameter),
+            "uri.errors.invalid_parameter" => Some(&config.uri.errors.invalid_parameter),
+            "uri.errors.unknown_parameter" => Some(&config.uri.errors.unknown_parameter),
+            "uri.errors.duplicate_parameter" => Some(&config.uri.errors.duplicate_parameter),
+            "uri.errors.issuer_mismatch" => Some(&config.uri.errors.issuer_mismatch),
+            "qr.errors.encode" => Some(&config.qr.errors.encode),
+            "qr.errors.invalid_module_size" => Some(&config.qr.errors.invalid_module_size),
+            "qr.errors.png" => Some(&config.qr.errors.png),
+            "migration.errors.invalid_uri" => Some(&config.migration.errors.invalid_uri),
+            "migration.errors.invalid_data" => Some(&config.migration.errors.invalid_data),
+            "migration.errors.unsupported_algorithm" => {
+                Some(&config.migration.errors.unsupported_algorithm)
+            }
+            "migration.errors.unsupported_digits" => {
+                Some(&config.migration.errors.unsupported_digits)
+            }
+            "migration.errors.unsupported_period" => {
+                Some(&config.migration.errors.unsupported_period)
+            }
+            "migration.errors.invalid_batch" => Some(&config.migration.errors.invalid_batch),
+            "scan.errors.image" => Some(&config.scan.errors.image),
+            "scan.errors.no_otp_code" => Some(&config.scan.errors.no_otp_code),
+            "secret.errors.random" => Some(&config.secret.errors.random),
+            "secret.errors.invalid_length" => Some(&config.secret.errors.invalid_length),
+            "secret.errors.invalid_encoding" => Some(&config.secret.errors.invalid_encoding),
+            "secret.errors.invalid_character" => Some(&config.secret.errors.invalid_character),
+            "algorithm.errors.empty" => Some(&config.algorithm.errors.empty),
+            "algorithm.errors.unknown_name" => Some(&config.algorithm.errors.unknown_name),
+            "algorithm.errors.unknown_oid" => Some(&config.algorithm.errors.unknown_oid),
+            "algorithm.errors.unsupported" => Some(&config.algorithm.errors.unsupported),
+            "algorithm.errors.feature_disabled" => Some(&config.algorithm.errors.feature_disabled),
             _ => None,
         }
     }
@@ -0,0 +1,67 @@
+use core::fmt;
+
+/// CN: 未启用i18n特性时使用的内置英文消息
+/// EN: Built-in English messages used when the i18n feature is disabled
+#[cfg(not(feature = "i18n"))]
+const DEFAULT_MESSAGES: &str = include_str!("locales/en.yml");
+
+/// CN: 按键名显示的本地化消息；启用i18n特性时按当前语言环境显示，否则显示内置英文消息，显示过程不进行堆分配
+/// EN: Localized message displayed by key; shown in the current locale with the i18n feature, otherwise as the
+/// EN: built-in English message, without heap allocation
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Message(&'static str);
+
+/// CN: 创建按键名（如"totp.errors.code_mismatch"）显示的消息
+/// EN: Create a message displayed by key (e.g. "totp.errors.code_mismatch")
+pub(crate) fn message(key: &'static str) -> Message {
+    Message(key)
+}
+
+impl fmt::Display for Message {
+    #[cfg(feature = "i18n")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let i18n = crate::utils::i18n::I18n::new();
+        f.write_str(i18n.get_message(self.0).map_or(self.0, String::as_str))
+    }
+
+    #[cfg(not(feature = "i18n"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(lookup(DEFAULT_MESSAGES, self.0).unwrap_or(self.0))
+    }
+}
+
+/// CN: 在按两个空格缩进的YAML消息文件中查找以点分隔的键，返回去除引号的值
+/// EN: Look up a dot-separated key in a YAML message file indented by two spaces, returning the unquoted value
+#[cfg(not(feature = "i18n"))]
+fn lookup(yaml: &'static str, key: &str) -> Option<&'static str> {
+    let mut segments = key.split('.');
+    let mut expected = segments.next()?;
+    let mut depth = 0;
+    for line in yaml.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = (line.len() - trimmed.len()) / 2;
+        if indent < depth {
+            // CN: 已离开匹配的父节点
+            // EN: Left the matched parent node
+            return None;
+        }
+        if indent > depth {
+            continue;
+        }
+        let (name, value) = trimmed.split_once(':')?;
+        if name != expected {
+            continue;
+        }
+        match segments.next() {
+            Some(next) => {
+                expected = next;
+                depth += 1;
+            }
+            None => return Some(value.trim().trim_matches('"')),
+        }
+    }
+    None
+}
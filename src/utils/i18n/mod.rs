@@ -1,2 +1,7 @@
+#[cfg(feature = "i18n")]
+#[allow(clippy::module_inception)]
 pub mod i18n;
+#[cfg(feature = "i18n")]
 pub use i18n::I18n;
+mod message;
+pub(crate) use message::message;
@@ -33,7 +33,7 @@ pub fn init_logger(log_path: &str) -> Result<(), LogError> {
     let log_file = FileAppender::builder()
         .encoder(Box::new(PatternEncoder::new("{d} {l} - {m}{n}")))
         .build(Path::new(log_path))
-        .map_err(|e| LogError::IoError(e))?;
+        .map_err(LogError::IoError)?;
 
     let config = Config::builder()
         .appender(Appender::builder().build("logfile", Box::new(log_file)))
@@ -1,2 +1,3 @@
+#[allow(clippy::module_inception)]
 pub mod log;
 pub use log::{init_logger, LogError};
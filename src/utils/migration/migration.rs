@@ -0,0 +1,483 @@
+use crate::utils::i18n::message;
+use crate::utils::secret::secret::Secret;
+use crate::utils::totp::totp::HashAlgorithm;
+use crate::utils::uri::uri::{OtpAuthUri, OtpType};
+use base64::alphabet::STANDARD;
+use base64::engine::general_purpose::{self, GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use std::collections::BTreeMap;
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// CN: 迁移URI的前缀
+/// EN: Prefix of migration URIs
+const MIGRATION_PREFIX: &str = "otpauth-migration://offline?";
+
+/// CN: 解码data参数使用的Base64引擎，填充可有可无
+/// EN: Base64 engine used for the data parameter, with optional padding
+const DATA_ENGINE: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// CN: Google Authenticator 迁移数据中TOTP的固定时间步长（秒）
+/// EN: Fixed TOTP time step in seconds in Google Authenticator migration data
+const MIGRATION_PERIOD: u32 = 30;
+
+/// CN: 迁移数据格式版本
+/// EN: Migration data format version
+const MIGRATION_VERSION: i32 = 1;
+
+/// CN: 迁移错误类型枚举
+/// EN: Migration error type enumeration
+#[derive(Debug, PartialEq, Eq)]
+pub enum MigrationError {
+    /// CN: 不是 otpauth-migration://offline URI 或缺少data参数
+    /// EN: Not an otpauth-migration://offline URI or the data parameter is missing
+    InvalidUri,
+    /// CN: data参数无法按Base64或Protobuf解码
+    /// EN: The data parameter cannot be decoded as Base64 or Protobuf
+    InvalidData,
+    /// CN: 不支持的哈希算法（导入时如MD5，导出时如SM3）
+    /// EN: Unsupported hash algorithm (such as MD5 on import or SM3 on export)
+    UnsupportedAlgorithm,
+    /// CN: 导出时验证码位数不是6或8
+    /// EN: Code digits other than 6 or 8 on export
+    UnsupportedDigits,
+    /// CN: 导出时TOTP时间步长不是30秒
+    /// EN: TOTP time step other than 30 seconds on export
+    UnsupportedPeriod,
+    /// CN: 分批数据不完整、重复或不属于同一批次，或分批大小为0
+    /// EN: Batch parts are incomplete, duplicated or from different batches, or the part size is 0
+    InvalidBatch,
+}
+
+/// CN: 为MigrationError实现显示特征，提供错误信息的国际化支持
+/// EN: Implement Display trait for MigrationError with internationalization support
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::InvalidUri => write!(f, "{}", message("migration.errors.invalid_uri")),
+            MigrationError::InvalidData => {
+                write!(f, "{}", message("migration.errors.invalid_data"))
+            }
+            MigrationError::UnsupportedAlgorithm => {
+                write!(f, "{}", message("migration.errors.unsupported_algorithm"))
+            }
+            MigrationError::UnsupportedDigits => {
+                write!(f, "{}", message("migration.errors.unsupported_digits"))
+            }
+            MigrationError::UnsupportedPeriod => {
+                write!(f, "{}", message("migration.errors.unsupported_period"))
+            }
+            MigrationError::InvalidBatch => {
+                write!(f, "{}", message("migration.errors.invalid_batch"))
+            }
+        }
+    }
+}
+
+/// CN: 迁移数据中的一个分批（一个二维码），多个分批共享同一批次标识
+/// EN: One part (one QR code) of migration data, parts of the same export share a batch id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationBatch {
+    /// CN: 本分批中的账户
+    /// EN: Accounts in this part
+    pub accounts: Vec<OtpAuthUri>,
+    /// CN: 迁移数据格式版本
+    /// EN: Migration data format version
+    pub version: i32,
+    /// CN: 分批总数
+    /// EN: Total number of parts
+    pub batch_size: i32,
+    /// CN: 本分批的序号（从0开始）
+    /// EN: Index of this part (starting at 0)
+    pub batch_index: i32,
+    /// CN: 批次标识
+    /// EN: Batch identifier
+    pub batch_id: i32,
+}
+
+/// CN: 解析 Google Authenticator 导出的 `otpauth-migration://offline?data=...` URI
+/// CN: 参数:
+/// CN: - uri: 迁移URI字符串
+/// CN: 返回:
+/// CN: - Ok(`Vec<OtpAuthUri>`): 迁移数据中的全部账户
+/// CN: - Err(MigrationError): URI或迁移数据错误
+///
+/// EN: Parse an `otpauth-migration://offline?data=...` URI exported by Google Authenticator
+/// EN: Parameters:
+/// EN: - uri: Migration URI string
+/// EN: Returns:
+/// EN: - Ok(`Vec<OtpAuthUri>`): All accounts in the migration data
+/// EN: - Err(MigrationError): Malformed URI or migration data
+pub fn decode_migration_uri(uri: &str) -> Result<Vec<OtpAuthUri>, MigrationError> {
+    Ok(decode_migration_batch(uri)?.accounts)
+}
+
+/// CN: 解析迁移URI，同时保留分批信息
+/// EN: Parse a migration URI, keeping its batch information
+pub fn decode_migration_batch(uri: &str) -> Result<MigrationBatch, MigrationError> {
+    let query = uri
+        .strip_prefix(MIGRATION_PREFIX)
+        .ok_or(MigrationError::InvalidUri)?;
+    let data = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("data="))
+        .ok_or(MigrationError::InvalidUri)?;
+    let data = percent_decode_str(data)
+        .decode_utf8()
+        .map_err(|_| MigrationError::InvalidData)?;
+    // CN: 迁移数据中包含明文密钥，使用后清零
+    // EN: The migration data holds plaintext secrets, so it is wiped after use
+    let payload = Zeroizing::new(
+        DATA_ENGINE
+            .decode(data.as_bytes())
+            .map_err(|_| MigrationError::InvalidData)?,
+    );
+
+    let mut batch = MigrationBatch {
+        accounts: Vec::new(),
+        version: 0,
+        batch_size: 0,
+        batch_index: 0,
+        batch_id: 0,
+    };
+    let mut reader = ProtoReader::new(&payload);
+    while let Some((field, value)) = reader.next_field()? {
+        match (field, value) {
+            (1, FieldValue::Bytes(parameters)) => {
+                batch.accounts.push(decode_parameters(parameters)?)
+            }
+            // CN: int32字段按其64位补码的低32位读取
+            // EN: int32 fields are read as the low 32 bits of their 64-bit two's complement
+            (2, FieldValue::Varint(value)) => batch.version = value as i32,
+            (3, FieldValue::Varint(value)) => batch.batch_size = value as i32,
+            (4, FieldValue::Varint(value)) => batch.batch_index = value as i32,
+            (5, FieldValue::Varint(value)) => batch.batch_id = value as i32,
+            _ => {}
+        }
+    }
+    Ok(batch)
+}
+
+/// CN: 解析多个分批迁移URI并按分批序号合并账户，URI顺序不限
+/// CN: 参数:
+/// CN: - uris: 同一批次的全部迁移URI
+/// CN: 返回:
+/// CN: - Ok(`Vec<OtpAuthUri>`): 按分批顺序合并后的全部账户
+/// CN: - Err(MigrationError): 任一URI错误，或分批不完整、重复、不属于同一批次
+///
+/// EN: Parse the migration URIs of a multi-part export and merge their accounts by part index, in any order
+/// EN: Parameters:
+/// EN: - uris: All migration URIs of one export
+/// EN: Returns:
+/// EN: - Ok(`Vec<OtpAuthUri>`): All accounts merged in part order
+/// EN: - Err(MigrationError): Any malformed URI, or incomplete, duplicated or mixed parts
+pub fn decode_migration_uris<'a, I>(uris: I) -> Result<Vec<OtpAuthUri>, MigrationError>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut parts = BTreeMap::new();
+    let mut expected: Option<(i32, i32)> = None;
+    for uri in uris {
+        let batch = decode_migration_batch(uri)?;
+        // CN: 未设置分批总数的数据视为单个分批
+        // EN: Data without a part count is treated as a single part
+        let identity = (batch.batch_id, batch.batch_size.max(1));
+        if *expected.get_or_insert(identity) != identity
+            || parts.insert(batch.batch_index, batch.accounts).is_some()
+        {
+            return Err(MigrationError::InvalidBatch);
+        }
+    }
+    let (_, batch_size) = expected.ok_or(MigrationError::InvalidBatch)?;
+    if !parts.keys().copied().eq(0..batch_size) {
+        return Err(MigrationError::InvalidBatch);
+    }
+    Ok(parts.into_values().flatten().collect())
+}
+
+/// CN: 将账户导出为 Google Authenticator 迁移URI，超过单个分批容量时拆分为多个URI
+/// CN: 参数:
+/// CN: - accounts: 待导出的账户
+/// CN: - max_per_part: 每个分批（二维码）最多包含的账户数
+/// CN: - batch_id: 批次标识，同一次导出的各分批共享
+/// CN: 返回:
+/// CN: - Ok(`Vec<String>`): 按分批序号排列的迁移URI
+/// CN: - Err(MigrationError): 账户参数不受迁移格式支持（SM3算法、6/8以外的位数、30秒以外的周期）
+///
+/// EN: Export accounts as Google Authenticator migration URIs, splitting into several URIs beyond one part's capacity
+/// EN: Parameters:
+/// EN: - accounts: Accounts to export
+/// EN: - max_per_part: Maximum number of accounts per part (QR code)
+/// EN: - batch_id: Batch identifier shared by all parts of one export
+/// EN: Returns:
+/// EN: - Ok(`Vec<String>`): Migration URIs ordered by part index
+/// EN: - Err(MigrationError): Account parameters unsupported by the format (SM3, digits other than 6/8,
+/// EN:   periods other than 30 seconds)
+pub fn encode_migration_uris(
+    accounts: &[OtpAuthUri],
+    max_per_part: usize,
+    batch_id: i32,
+) -> Result<Vec<String>, MigrationError> {
+    if max_per_part == 0 {
+        return Err(MigrationError::InvalidBatch);
+    }
+    let encoded = accounts
+        .iter()
+        .map(encode_parameters)
+        .collect::<Result<Vec<_>, _>>()?;
+    let chunks: Vec<&[Zeroizing<Vec<u8>>]> = if encoded.is_empty() {
+        vec![&[]]
+    } else {
+        encoded.chunks(max_per_part).collect()
+    };
+    let batch_size = i32::try_from(chunks.len()).map_err(|_| MigrationError::InvalidBatch)?;
+
+    Ok(chunks
+        .into_iter()
+        .enumerate()
+        .map(|(batch_index, parameters)| {
+            let mut payload = Zeroizing::new(Vec::new());
+            for message in parameters {
+                write_bytes(&mut payload, 1, message);
+            }
+            write_varint_field(&mut payload, 2, MIGRATION_VERSION as u64);
+            write_varint_field(&mut payload, 3, batch_size as u64);
+            write_varint_field(&mut payload, 4, batch_index as u64);
+            // CN: 负的int32按符号扩展后的64位补码编码
+            // EN: Negative int32 values are encoded as their sign-extended 64-bit two's complement
+            write_varint_field(&mut payload, 5, batch_id as i64 as u64);
+            let data = general_purpose::STANDARD.encode(payload.as_slice());
+            format!(
+                "{}data={}",
+                MIGRATION_PREFIX,
+                utf8_percent_encode(&data, NON_ALPHANUMERIC)
+            )
+        })
+        .collect())
+}
+
+/// CN: 将账户编码为 OtpParameters 消息
+/// EN: Encode an account as an OtpParameters message
+fn encode_parameters(account: &OtpAuthUri) -> Result<Zeroizing<Vec<u8>>, MigrationError> {
+    if account.secret.is_empty() {
+        return Err(MigrationError::InvalidData);
+    }
+    let algorithm = match account.algorithm {
+        HashAlgorithm::SHA1 => 1,
+        HashAlgorithm::SHA256 => 2,
+        HashAlgorithm::SHA512 => 3,
+        _ => return Err(MigrationError::UnsupportedAlgorithm),
+    };
+    let digits = match account.digits {
+        6 => 1,
+        8 => 2,
+        _ => return Err(MigrationError::UnsupportedDigits),
+    };
+
+    let mut message = Zeroizing::new(Vec::new());
+    write_bytes(&mut message, 1, account.secret.as_bytes());
+    write_bytes(&mut message, 2, account.account_name.as_bytes());
+    if let Some(issuer) = &account.issuer {
+        write_bytes(&mut message, 3, issuer.as_bytes());
+    }
+    write_varint_field(&mut message, 4, algorithm);
+    write_varint_field(&mut message, 5, digits);
+    match account.otp_type {
+        OtpType::Totp { period } if period == MIGRATION_PERIOD => {
+            write_varint_field(&mut message, 6, 2);
+        }
+        OtpType::Totp { .. } => return Err(MigrationError::UnsupportedPeriod),
+        OtpType::Hotp { counter } => {
+            write_varint_field(&mut message, 6, 1);
+            write_varint_field(&mut message, 7, counter);
+        }
+    }
+    Ok(message)
+}
+
+/// CN: 写入varint线型的字段
+/// EN: Write a field of the varint wire type
+fn write_varint_field(buffer: &mut Vec<u8>, field: u64, value: u64) {
+    write_varint(buffer, field << 3);
+    write_varint(buffer, value);
+}
+
+/// CN: 写入长度前缀线型的字段
+/// EN: Write a field of the length-delimited wire type
+fn write_bytes(buffer: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    write_varint(buffer, field << 3 | 2);
+    write_varint(buffer, bytes.len() as u64);
+    buffer.extend_from_slice(bytes);
+}
+
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// CN: 将 OtpParameters 消息转换为otpauth URI
+/// EN: Convert an OtpParameters message into an otpauth URI
+fn decode_parameters(message: &[u8]) -> Result<OtpAuthUri, MigrationError> {
+    let mut secret: &[u8] = &[];
+    let mut name = String::new();
+    let mut issuer = String::new();
+    let mut algorithm = HashAlgorithm::SHA1;
+    let mut digits = 6;
+    let mut is_hotp = false;
+    let mut counter = 0;
+
+    let mut reader = ProtoReader::new(message);
+    while let Some((field, value)) = reader.next_field()? {
+        match (field, value) {
+            (1, FieldValue::Bytes(bytes)) => secret = bytes,
+            (2, FieldValue::Bytes(bytes)) => name = utf8(bytes)?,
+            (3, FieldValue::Bytes(bytes)) => issuer = utf8(bytes)?,
+            // CN: 枚举值0表示未指定，按默认值处理；未知取值视为数据错误
+            // EN: Enum value 0 means unspecified and keeps the default; unknown values are a decode error
+            (4, FieldValue::Varint(value)) => {
+                algorithm = match value {
+                    0 | 1 => HashAlgorithm::SHA1,
+                    2 => HashAlgorithm::SHA256,
+                    3 => HashAlgorithm::SHA512,
+                    // CN: MD5
+                    // EN: MD5
+                    4 => return Err(MigrationError::UnsupportedAlgorithm),
+                    _ => return Err(MigrationError::InvalidData),
+                }
+            }
+            (5, FieldValue::Varint(value)) => {
+                digits = match value {
+                    0 | 1 => 6,
+                    2 => 8,
+                    _ => return Err(MigrationError::InvalidData),
+                }
+            }
+            (6, FieldValue::Varint(value)) => {
+                is_hotp = match value {
+                    1 => true,
+                    0 | 2 => false,
+                    _ => return Err(MigrationError::InvalidData),
+                }
+            }
+            (7, FieldValue::Varint(value)) => counter = value,
+            _ => {}
+        }
+    }
+    if secret.is_empty() {
+        return Err(MigrationError::InvalidData);
+    }
+
+    // CN: 名称通常为 `发行方:账户名` 形式，发行方与issuer字段一致时去除前缀
+    // EN: The name is usually `issuer:account`, the prefix is dropped when it equals the issuer field
+    let account_name = match name.split_once(':') {
+        Some((prefix, account)) if prefix == issuer => account.trim_start().to_string(),
+        _ => name,
+    };
+    let otp_type = if is_hotp {
+        OtpType::Hotp { counter }
+    } else {
+        OtpType::Totp {
+            period: MIGRATION_PERIOD,
+        }
+    };
+    Ok(OtpAuthUri {
+        otp_type,
+        issuer: (!issuer.is_empty()).then_some(issuer),
+        account_name,
+        secret: Secret::from_bytes(secret),
+        algorithm,
+        digits,
+    })
+}
+
+/// CN: 将字节解码为UTF-8字符串
+/// EN: Decode bytes as a UTF-8 string
+fn utf8(bytes: &[u8]) -> Result<String, MigrationError> {
+    String::from_utf8(bytes.to_vec()).map_err(|_| MigrationError::InvalidData)
+}
+
+/// CN: Protobuf字段值
+/// EN: Protobuf field value
+enum FieldValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    Fixed,
+}
+
+/// CN: 仅支持迁移数据所需线型的最小Protobuf读取器
+/// EN: Minimal Protobuf reader supporting the wire types needed by migration data
+struct ProtoReader<'a> {
+    buffer: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ProtoReader<'a> {
+    fn new(buffer: &'a [u8]) -> Self {
+        Self {
+            buffer,
+            position: 0,
+        }
+    }
+
+    /// CN: 读取下一个字段，返回字段号与值，缓冲区结束时返回None
+    /// EN: Read the next field as its number and value, returning None at the end of the buffer
+    fn next_field(&mut self) -> Result<Option<(u64, FieldValue<'a>)>, MigrationError> {
+        if self.position == self.buffer.len() {
+            return Ok(None);
+        }
+        let key = self.read_varint()?;
+        let value = match key & 0x7 {
+            0 => FieldValue::Varint(self.read_varint()?),
+            1 => {
+                self.take(8)?;
+                FieldValue::Fixed
+            }
+            2 => {
+                let length = usize::try_from(self.read_varint()?)
+                    .map_err(|_| MigrationError::InvalidData)?;
+                FieldValue::Bytes(self.take(length)?)
+            }
+            5 => {
+                self.take(4)?;
+                FieldValue::Fixed
+            }
+            _ => return Err(MigrationError::InvalidData),
+        };
+        Ok(Some((key >> 3, value)))
+    }
+
+    fn read_varint(&mut self) -> Result<u64, MigrationError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .buffer
+                .get(self.position)
+                .ok_or(MigrationError::InvalidData)?;
+            self.position += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(MigrationError::InvalidData)
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], MigrationError> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.buffer.len())
+            .ok_or(MigrationError::InvalidData)?;
+        let bytes = &self.buffer[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+}
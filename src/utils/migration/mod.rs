@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod migration;
+pub use migration::{
+    decode_migration_batch, decode_migration_uri, decode_migration_uris, encode_migration_uris,
+    MigrationBatch, MigrationError,
+};
@@ -4,6 +4,22 @@
 //! EN: Utility Module
 //! EN: Contains various utility functions and modules
 
+#[cfg(feature = "std")]
+pub mod gm;
+pub mod hotp;
 pub mod i18n;
+#[cfg(feature = "logging")]
 pub mod log;
+#[cfg(feature = "std")]
+pub mod migration;
+#[cfg(feature = "std")]
+pub mod ocra;
+#[cfg(feature = "qr")]
+pub mod qr;
+#[cfg(feature = "scan")]
+pub mod scan;
+#[cfg(feature = "std")]
+pub mod secret;
 pub mod totp;
+#[cfg(feature = "std")]
+pub mod uri;
@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod ocra;
+pub use ocra::{
+    generate_ocra, generate_ocra_with_clock, verify_ocra, verify_ocra_with_clock, OcraError,
+    OcraInput, OcraSuite, QuestionFormat,
+};
@@ -0,0 +1,3 @@
+#[allow(clippy::module_inception)]
+pub mod qr;
+pub use qr::{render_png, render_svg, render_terminal, QrError};
@@ -0,0 +1,140 @@
+use crate::utils::i18n::message;
+use crate::utils::uri::uri::OtpAuthUri;
+use qrcode::render::{svg, unicode};
+use qrcode::{Color, EcLevel, QrCode};
+use std::fmt;
+
+/// CN: 二维码四周静区的模块数（ISO/IEC 18004 要求至少4个模块）
+/// EN: Number of quiet-zone modules around the code (ISO/IEC 18004 requires at least 4)
+const QUIET_ZONE: usize = 4;
+
+/// CN: PNG图像每个模块边长的上限（像素），最大版本的二维码在此尺寸下约为5920×5920像素
+/// EN: Upper bound on the PNG module edge length in pixels, at which the largest QR version is about 5920×5920 pixels
+pub const MAX_MODULE_SIZE: u32 = 32;
+
+/// CN: 二维码错误类型枚举
+/// EN: QR code error type enumeration
+#[derive(Debug, PartialEq, Eq)]
+pub enum QrError {
+    /// CN: URI过长或无法编码为二维码
+    /// EN: URI is too long or cannot be encoded as a QR code
+    Encode,
+    /// CN: 模块尺寸无效（必须大于0，PNG图像还不能超过MAX_MODULE_SIZE）
+    /// EN: Invalid module size (must be greater than 0, and no more than MAX_MODULE_SIZE for PNG images)
+    InvalidModuleSize,
+    /// CN: PNG编码失败
+    /// EN: PNG encoding failed
+    Png,
+}
+
+/// CN: 为QrError实现显示特征，提供错误信息的国际化支持
+/// EN: Implement Display trait for QrError with internationalization support
+impl fmt::Display for QrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QrError::Encode => write!(f, "{}", message("qr.errors.encode")),
+            QrError::InvalidModuleSize => write!(f, "{}", message("qr.errors.invalid_module_size")),
+            QrError::Png => write!(f, "{}", message("qr.errors.png")),
+        }
+    }
+}
+
+/// CN: 将URI渲染为Unicode半块字符组成的终端二维码
+/// CN: 暗色模块以空格输出、亮色模块以方块输出，适用于深色背景的终端
+///
+/// EN: Render the URI as a terminal QR code made of Unicode half-block characters
+/// EN: Dark modules are printed as spaces and light modules as blocks, suiting dark-background terminals
+pub fn render_terminal(uri: &OtpAuthUri) -> Result<String, QrError> {
+    let code = encode(uri)?;
+    Ok(code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .quiet_zone(true)
+        .build())
+}
+
+/// CN: 将URI渲染为SVG文档
+/// CN: 参数:
+/// CN: - uri: 待渲染的otpauth URI
+/// CN: - module_size: 每个模块的边长（像素）
+/// CN: 返回:
+/// CN: - Ok(String): SVG文档
+/// CN: - Err(QrError): 渲染过程中的错误
+///
+/// EN: Render the URI as an SVG document
+/// EN: Parameters:
+/// EN: - uri: otpauth URI to render
+/// EN: - module_size: Edge length of each module in pixels
+/// EN: Returns:
+/// EN: - Ok(String): SVG document
+/// EN: - Err(QrError): Errors during rendering
+pub fn render_svg(uri: &OtpAuthUri, module_size: u32) -> Result<String, QrError> {
+    if module_size == 0 {
+        return Err(QrError::InvalidModuleSize);
+    }
+    let code = encode(uri)?;
+    Ok(code
+        .render::<svg::Color>()
+        .module_dimensions(module_size, module_size)
+        .quiet_zone(true)
+        .build())
+}
+
+/// CN: 将URI渲染为8位灰度PNG图像
+/// CN: 参数:
+/// CN: - uri: 待渲染的otpauth URI
+/// CN: - module_size: 每个模块的边长（像素，1至MAX_MODULE_SIZE）
+/// CN: 返回:
+/// CN: - Ok(`Vec<u8>`): PNG文件字节
+/// CN: - Err(QrError): 渲染过程中的错误
+///
+/// EN: Render the URI as an 8-bit grayscale PNG image
+/// EN: Parameters:
+/// EN: - uri: otpauth URI to render
+/// EN: - module_size: Edge length of each module in pixels (1 to MAX_MODULE_SIZE)
+/// EN: Returns:
+/// EN: - Ok(`Vec<u8>`): PNG file bytes
+/// EN: - Err(QrError): Errors during rendering
+pub fn render_png(uri: &OtpAuthUri, module_size: u32) -> Result<Vec<u8>, QrError> {
+    // CN: 限制模块尺寸，使像素缓冲区大小有界
+    // EN: Bound the module size so the pixel buffer stays bounded
+    if !(1..=MAX_MODULE_SIZE).contains(&module_size) {
+        return Err(QrError::InvalidModuleSize);
+    }
+    let code = encode(uri)?;
+    let width = code.width();
+    let colors = code.to_colors();
+    let scale = module_size as usize;
+    let side = (width + 2 * QUIET_ZONE) * scale;
+
+    // CN: 先按模块生成一行像素，再按模块尺寸重复写入，静区保持白色
+    // EN: Build each pixel row per module and repeat it by the module size, keeping the quiet zone white
+    let mut pixels = vec![0xffu8; side * side];
+    for (y, row) in colors.chunks(width).enumerate() {
+        let top = (y + QUIET_ZONE) * scale;
+        for (x, color) in row.iter().enumerate() {
+            if *color == Color::Dark {
+                let left = (x + QUIET_ZONE) * scale;
+                for line in pixels[top * side..(top + scale) * side].chunks_mut(side) {
+                    line[left..left + scale].fill(0);
+                }
+            }
+        }
+    }
+
+    let mut png_bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_bytes, side as u32, side as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|_| QrError::Png)?;
+    writer.write_image_data(&pixels).map_err(|_| QrError::Png)?;
+    writer.finish().map_err(|_| QrError::Png)?;
+    Ok(png_bytes)
+}
+
+/// CN: 使用中等纠错等级将URI编码为二维码
+/// EN: Encode the URI as a QR code with medium error correction
+fn encode(uri: &OtpAuthUri) -> Result<QrCode, QrError> {
+    QrCode::with_error_correction_level(uri.to_string(), EcLevel::M).map_err(|_| QrError::Encode)
+}
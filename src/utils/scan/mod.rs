@@ -0,0 +1,3 @@
+#[allow(clippy::module_inception)]
+pub mod scan;
+pub use scan::{scan_image, scan_image_file, ScanError};
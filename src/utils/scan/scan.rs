@@ -0,0 +1,103 @@
+use crate::utils::i18n::message;
+use crate::utils::migration::migration::{decode_migration_uri, MigrationError};
+use crate::utils::uri::uri::{OtpAuthUri, ParseMode, UriError};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// CN: 二维码图片导入错误类型枚举
+/// EN: QR code image import error type enumeration
+#[derive(Debug)]
+pub enum ScanError {
+    /// CN: 图片读取或解码失败
+    /// EN: Failed to read or decode the image
+    Image,
+    /// CN: 图片中没有可识别的otpauth二维码
+    /// EN: No recognisable otpauth QR code in the image
+    NoOtpCode,
+    /// CN: otpauth URI解析错误
+    /// EN: otpauth URI parsing error
+    Uri(UriError),
+    /// CN: 迁移数据解析错误
+    /// EN: Migration data parsing error
+    Migration(MigrationError),
+}
+
+/// CN: 为ScanError实现显示特征，提供错误信息的国际化支持
+/// EN: Implement Display trait for ScanError with internationalization support
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::Image => write!(f, "{}", message("scan.errors.image")),
+            ScanError::NoOtpCode => write!(f, "{}", message("scan.errors.no_otp_code")),
+            ScanError::Uri(e) => write!(f, "{}", e),
+            ScanError::Migration(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<UriError> for ScanError {
+    fn from(e: UriError) -> Self {
+        ScanError::Uri(e)
+    }
+}
+
+impl From<MigrationError> for ScanError {
+    fn from(e: MigrationError) -> Self {
+        ScanError::Migration(e)
+    }
+}
+
+/// CN: 读取PNG或JPEG图片文件并导入其中全部otpauth二维码的账户
+/// EN: Read a PNG or JPEG image file and import the accounts of every otpauth QR code in it
+pub fn scan_image_file<P: AsRef<Path>>(path: P) -> Result<Vec<OtpAuthUri>, ScanError> {
+    let bytes = fs::read(path).map_err(|_| ScanError::Image)?;
+    scan_image(&bytes)
+}
+
+/// CN: 从PNG或JPEG图片字节中识别全部二维码，并解析其中的otpauth与otpauth-migration内容
+/// CN: 参数:
+/// CN: - image_bytes: PNG或JPEG图片字节
+/// CN: 返回:
+/// CN: - Ok(`Vec<OtpAuthUri>`): 按二维码识别顺序排列的账户，可通过secret与totp_config生成验证码
+/// CN: - Err(ScanError): 图片无法解码、没有otpauth二维码或内容解析错误
+///
+/// EN: Detect every QR code in PNG or JPEG image bytes and parse their otpauth and otpauth-migration payloads
+/// EN: Parameters:
+/// EN: - image_bytes: PNG or JPEG image bytes
+/// EN: Returns:
+/// EN: - Ok(`Vec<OtpAuthUri>`): Accounts in detection order, ready for code generation via secret and totp_config
+/// EN: - Err(ScanError): Undecodable image, no otpauth QR code or malformed payload
+pub fn scan_image(image_bytes: &[u8]) -> Result<Vec<OtpAuthUri>, ScanError> {
+    let image = image::load_from_memory(image_bytes)
+        .map_err(|_| ScanError::Image)?
+        .to_luma8();
+    let mut prepared = rqrr::PreparedImage::prepare_from_greyscale(
+        image.width() as usize,
+        image.height() as usize,
+        |x, y| image.get_pixel(x as u32, y as u32).0[0],
+    );
+
+    // CN: 与otpauth无关或无法解码的二维码（如截图中的其他二维码）会被跳过
+    // EN: QR codes unrelated to otpauth or failing to decode (e.g. others in a screenshot) are skipped
+    let mut accounts = Vec::new();
+    let mut found = false;
+    for grid in prepared.detect_grids() {
+        let Ok((_, content)) = grid.decode() else {
+            continue;
+        };
+        let scheme = content.split(':').next().unwrap_or_default();
+        if scheme.eq_ignore_ascii_case("otpauth-migration") {
+            accounts.extend(decode_migration_uri(&content)?);
+        } else if scheme.eq_ignore_ascii_case("otpauth") {
+            accounts.push(OtpAuthUri::parse(&content, ParseMode::Lenient)?);
+        } else {
+            continue;
+        }
+        found = true;
+    }
+    if !found {
+        return Err(ScanError::NoOtpCode);
+    }
+    Ok(accounts)
+}
@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod secret;
+pub use secret::{
+    generate_secret, generate_secret_with_length, recommended_secret_length, Secret,
+    SecretEncoding, SecretError, SecretSource,
+};
@@ -0,0 +1,404 @@
+use crate::utils::i18n::message;
+use crate::utils::totp::totp::{HashAlgorithm, TotpError};
+use base32::Alphabet::Rfc4648;
+use base64::alphabet::STANDARD;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use std::fmt;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
+
+/// CN: 密钥的最小长度（RFC 4226 §4 要求至少128比特）
+/// EN: Minimum secret length (RFC 4226 §4 requires at least 128 bits)
+const MIN_SECRET_LENGTH: usize = 16;
+
+/// CN: 解码Base64密钥使用的引擎，填充可有可无
+/// EN: Engine used to decode Base64 secrets, with optional padding
+const BASE64_ENGINE: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// CN: 密钥生成错误类型枚举
+/// EN: Secret generation error type enumeration
+#[derive(Debug, PartialEq, Eq)]
+pub enum SecretError {
+    /// CN: 操作系统随机数生成器不可用
+    /// EN: The operating system random number generator is unavailable
+    Random,
+    /// CN: 密钥长度无效（少于128比特）
+    /// EN: Invalid secret length (less than 128 bits)
+    InvalidLength,
+    /// CN: 密钥编码无效或解码结果为空
+    /// EN: Invalid secret encoding or empty decoded secret
+    InvalidEncoding,
+    /// CN: 密钥含非法字符，附带该字符及其在输入中的位置（按字符计，从0开始）
+    /// EN: Invalid character in the secret, with the character and its position in the input (in characters, from 0)
+    InvalidCharacter { position: usize, character: char },
+}
+
+/// CN: 为SecretError实现显示特征，提供错误信息的国际化支持
+/// EN: Implement Display trait for SecretError with internationalization support
+impl fmt::Display for SecretError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecretError::Random => {
+                write!(f, "{}", message("secret.errors.random"))
+            }
+            SecretError::InvalidLength => write!(f, "{}", message("secret.errors.invalid_length")),
+            SecretError::InvalidEncoding => {
+                write!(f, "{}", message("secret.errors.invalid_encoding"))
+            }
+            SecretError::InvalidCharacter {
+                position,
+                character,
+            } => write!(
+                f,
+                "{}: '{}' ({})",
+                message("secret.errors.invalid_character"),
+                character,
+                position
+            ),
+        }
+    }
+}
+
+/// CN: 密钥的文本编码
+/// EN: Text encoding of a secret
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretEncoding {
+    /// CN: RFC 4648 Base32
+    /// EN: RFC 4648 Base32
+    Base32,
+    /// CN: 十六进制
+    /// EN: Hexadecimal
+    Hex,
+    /// CN: 标准Base64
+    /// EN: Standard Base64
+    Base64,
+}
+
+/// CN: 持有已解码密钥字节的密钥类型，释放时清零内存，调试与显示输出时隐藏内容
+/// EN: Secret key owning the decoded key bytes, wiped from memory on drop and redacted in Debug and Display
+#[derive(Clone)]
+pub struct Secret {
+    /// CN: 密钥原始字节
+    /// EN: Raw secret bytes
+    bytes: Vec<u8>,
+}
+
+impl Secret {
+    /// CN: 从原始字节创建密钥
+    /// EN: Create a secret from raw bytes
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    /// CN: 从无填充的RFC 4648 Base32字符串创建密钥
+    /// EN: Create a secret from an unpadded RFC 4648 Base32 string
+    pub fn from_base32(encoded: &str) -> Result<Self, SecretError> {
+        base32::decode(Rfc4648 { padding: false }, encoded)
+            .filter(|bytes| !bytes.is_empty())
+            .map(Self::from)
+            .ok_or(SecretError::InvalidEncoding)
+    }
+
+    /// CN: 从十六进制字符串创建密钥（不区分大小写）
+    /// EN: Create a secret from a hexadecimal string (case-insensitive)
+    pub fn from_hex(encoded: &str) -> Result<Self, SecretError> {
+        if encoded.is_empty()
+            || !encoded.len().is_multiple_of(2)
+            || !encoded.bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            return Err(SecretError::InvalidEncoding);
+        }
+        let mut secret = Self::from(Vec::with_capacity(encoded.len() / 2));
+        for pair in encoded.as_bytes().chunks_exact(2) {
+            let high = (pair[0] as char).to_digit(16).unwrap_or_default();
+            let low = (pair[1] as char).to_digit(16).unwrap_or_default();
+            secret.bytes.push((high << 4 | low) as u8);
+        }
+        Ok(secret)
+    }
+
+    /// CN: 从标准Base64字符串创建密钥（填充可有可无）
+    /// EN: Create a secret from a standard Base64 string (padding optional)
+    pub fn from_base64(encoded: &str) -> Result<Self, SecretError> {
+        BASE64_ENGINE
+            .decode(encoded)
+            .ok()
+            .filter(|bytes| !bytes.is_empty())
+            .map(Self::from)
+            .ok_or(SecretError::InvalidEncoding)
+    }
+
+    /// CN: 宽松解析密钥文本，自动识别编码
+    /// CN: 忽略空白与连字符分组，Base32不区分大小写且允许填充，带0x前缀时按十六进制解析
+    /// CN: 无前缀时依次尝试Base32、十六进制与Base64，三者均可解码时以靠前者为准
+    /// CN: 参数:
+    /// CN: - input: 密钥文本，如 "jbsw y3dp ehpk 3pxp"
+    /// CN: 返回:
+    /// CN: - Ok((Secret, SecretEncoding)): 密钥及识别出的编码
+    /// CN: - Err(SecretError): 含非法字符时返回其位置，否则返回InvalidEncoding
+    ///
+    /// EN: Leniently parse secret text, detecting its encoding
+    /// EN: Whitespace and hyphen grouping are ignored, Base32 is case-insensitive with optional padding, and a 0x prefix forces hexadecimal
+    /// EN: Without a prefix Base32, hexadecimal and Base64 are tried in that order, the earlier one winning when several decode
+    /// EN: Parameters:
+    /// EN: - input: Secret text, e.g. "jbsw y3dp ehpk 3pxp"
+    /// EN: Returns:
+    /// EN: - Ok((Secret, SecretEncoding)): Secret and the detected encoding
+    /// EN: - Err(SecretError): Position of an invalid character if any, otherwise InvalidEncoding
+    pub fn parse(input: &str) -> Result<(Self, SecretEncoding), SecretError> {
+        let normalized = Normalized::new(input);
+        if normalized.has_hex_prefix() {
+            return decode_hex(&normalized).map(|secret| (secret, SecretEncoding::Hex));
+        }
+        let base32_error = match decode_base32(&normalized) {
+            Ok(secret) => return Ok((secret, SecretEncoding::Base32)),
+            Err(e) => e,
+        };
+        if let Ok(secret) = decode_hex(&normalized) {
+            return Ok((secret, SecretEncoding::Hex));
+        }
+        if let Ok(secret) = decode_base64(&normalized) {
+            return Ok((secret, SecretEncoding::Base64));
+        }
+
+        // CN: 优先指出不属于任何受支持字母表的字符，否则沿用Base32的错误
+        // EN: Point at a character outside every supported alphabet first, otherwise keep the Base32 error
+        match normalized
+            .text
+            .chars()
+            .position(|c| !c.is_ascii_alphanumeric() && !matches!(c, '+' | '/' | '='))
+        {
+            Some(index) => Err(normalized.invalid_character(index)),
+            None => Err(base32_error),
+        }
+    }
+
+    /// CN: 按指定编码宽松解析密钥文本，忽略空白与连字符分组
+    /// EN: Leniently parse secret text in the given encoding, ignoring whitespace and hyphen grouping
+    pub fn parse_as(input: &str, encoding: SecretEncoding) -> Result<Self, SecretError> {
+        let normalized = Normalized::new(input);
+        match encoding {
+            SecretEncoding::Base32 => decode_base32(&normalized),
+            SecretEncoding::Hex => decode_hex(&normalized),
+            SecretEncoding::Base64 => decode_base64(&normalized),
+        }
+    }
+
+    /// CN: 获取密钥原始字节
+    /// EN: Get the raw secret bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// CN: 密钥字节数
+    /// EN: Number of secret bytes
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// CN: 密钥是否为空
+    /// EN: Whether the secret is empty
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// CN: 编码为无填充的Base32字符串，可直接传给 `generate_totp_code`
+    /// EN: Encode as an unpadded Base32 string, ready to pass to `generate_totp_code`
+    pub fn to_base32(&self) -> String {
+        base32::encode(Rfc4648 { padding: false }, &self.bytes)
+    }
+}
+
+/// CN: 直接接管字节缓冲区，避免留下未清零的副本
+/// EN: Take ownership of the byte buffer, leaving no unwiped copy behind
+impl From<Vec<u8>> for Secret {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+}
+
+/// CN: 释放时清零密钥字节
+/// EN: Wipe the secret bytes on drop
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+/// CN: 以常量时间比较密钥
+/// EN: Compare secrets in constant time
+impl PartialEq for Secret {
+    fn eq(&self, other: &Self) -> bool {
+        bool::from(self.bytes.ct_eq(&other.bytes))
+    }
+}
+
+impl Eq for Secret {}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret([REDACTED])")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
+
+/// CN: 去除空白与连字符分组后的密钥文本，记录每个字符在原始输入中的位置
+/// EN: Secret text stripped of whitespace and hyphen grouping, remembering each character's position in the input
+struct Normalized {
+    /// CN: 规整后的文本
+    /// EN: Normalized text
+    text: Zeroizing<String>,
+    /// CN: 规整后每个字符在原始输入中的位置
+    /// EN: Position in the input of each normalized character
+    positions: Vec<usize>,
+}
+
+impl Normalized {
+    fn new(input: &str) -> Self {
+        let mut text = Zeroizing::new(String::with_capacity(input.len()));
+        let mut positions = Vec::with_capacity(input.len());
+        for (position, c) in input.chars().enumerate() {
+            if !c.is_whitespace() && c != '-' {
+                text.push(c);
+                positions.push(position);
+            }
+        }
+        Self { text, positions }
+    }
+
+    fn has_hex_prefix(&self) -> bool {
+        self.text.starts_with("0x") || self.text.starts_with("0X")
+    }
+
+    /// CN: 构造指向规整后第index个字符的错误
+    /// EN: Build an error pointing at the index-th normalized character
+    fn invalid_character(&self, index: usize) -> SecretError {
+        SecretError::InvalidCharacter {
+            position: self.positions[index],
+            character: self.text.chars().nth(index).unwrap_or_default(),
+        }
+    }
+}
+
+/// CN: 不区分大小写地解码Base32，填充可选但必须补齐到8个字符的倍数
+/// EN: Decode case-insensitive Base32, padding being optional but required to complete a multiple of 8 characters
+fn decode_base32(normalized: &Normalized) -> Result<Secret, SecretError> {
+    let data = normalized.text.trim_end_matches('=');
+    if let Some(index) = data
+        .chars()
+        .position(|c| !matches!(c.to_ascii_uppercase(), 'A'..='Z' | '2'..='7'))
+    {
+        return Err(normalized.invalid_character(index));
+    }
+    let padding = normalized.text.len() - data.len();
+    let padded = padding == 0 || (data.len() + padding).is_multiple_of(8) && padding < 8;
+    if data.is_empty() || matches!(data.len() % 8, 1 | 3 | 6) || !padded {
+        return Err(SecretError::InvalidEncoding);
+    }
+    Secret::from_base32(&Zeroizing::new(data.to_ascii_uppercase()))
+}
+
+/// CN: 解码十六进制，允许0x前缀
+/// EN: Decode hexadecimal, allowing a 0x prefix
+fn decode_hex(normalized: &Normalized) -> Result<Secret, SecretError> {
+    let offset = if normalized.has_hex_prefix() { 2 } else { 0 };
+    let digits = &normalized.text[offset..];
+    if let Some(index) = digits.chars().position(|c| !c.is_ascii_hexdigit()) {
+        return Err(normalized.invalid_character(offset + index));
+    }
+    Secret::from_hex(digits)
+}
+
+/// CN: 解码标准Base64，填充可选且至多两个
+/// EN: Decode standard Base64, padding being optional and at most two characters
+fn decode_base64(normalized: &Normalized) -> Result<Secret, SecretError> {
+    let data = normalized.text.trim_end_matches('=');
+    if let Some(index) = data
+        .chars()
+        .position(|c| !c.is_ascii_alphanumeric() && !matches!(c, '+' | '/'))
+    {
+        return Err(normalized.invalid_character(index));
+    }
+    if normalized.text.len() - data.len() > 2 {
+        return Err(normalized.invalid_character(data.len() + 2));
+    }
+    Secret::from_base64(&normalized.text)
+}
+
+/// CN: 可用作密钥的类型特征，所有生成与校验接口均接受实现该特征的类型
+/// CN: 字符串按宽松Base32解码（不区分大小写，允许空白、连字符分组与填充），Secret则直接使用其字节
+///
+/// EN: Trait for types usable as a secret, accepted by all generation and verification APIs
+/// EN: Strings are decoded as lenient Base32 (case-insensitive, allowing whitespace, hyphen grouping and padding), while a Secret is used as is
+pub trait SecretSource {
+    /// CN: 获取密钥，空密钥返回InvalidKeyLength
+    /// EN: Get the secret, returning InvalidKeyLength for an empty one
+    fn to_secret(&self) -> Result<Secret, TotpError>;
+}
+
+impl SecretSource for str {
+    fn to_secret(&self) -> Result<Secret, TotpError> {
+        Secret::parse_as(self, SecretEncoding::Base32).map_err(|_| TotpError::Base32DecodeError)
+    }
+}
+
+impl SecretSource for String {
+    fn to_secret(&self) -> Result<Secret, TotpError> {
+        self.as_str().to_secret()
+    }
+}
+
+impl SecretSource for Secret {
+    fn to_secret(&self) -> Result<Secret, TotpError> {
+        if self.is_empty() {
+            return Err(TotpError::InvalidKeyLength);
+        }
+        Ok(self.clone())
+    }
+}
+
+/// CN: 获取哈希算法推荐的密钥长度（字节），与HMAC输出长度一致（RFC 6238 附录A）
+/// EN: Get the recommended secret length in bytes for a hash algorithm, matching its HMAC output (RFC 6238 Appendix A)
+pub fn recommended_secret_length(hash_algorithm: HashAlgorithm) -> usize {
+    hash_algorithm.output_size()
+}
+
+/// CN: 使用操作系统的密码学安全随机数生成器，按哈希算法推荐的长度生成密钥
+/// CN: 参数:
+/// CN: - hash_algorithm: 密钥将要使用的哈希算法
+/// CN: 返回:
+/// CN: - Ok(Secret): 生成的密钥
+/// CN: - Err(SecretError): 随机数生成器不可用
+///
+/// EN: Generate a secret of the recommended length for a hash algorithm using the OS CSPRNG
+/// EN: Parameters:
+/// EN: - hash_algorithm: Hash algorithm the secret will be used with
+/// EN: Returns:
+/// EN: - Ok(Secret): Generated secret
+/// EN: - Err(SecretError): The random number generator is unavailable
+pub fn generate_secret(hash_algorithm: HashAlgorithm) -> Result<Secret, SecretError> {
+    generate_secret_with_length(recommended_secret_length(hash_algorithm))
+}
+
+/// CN: 使用操作系统的密码学安全随机数生成器生成指定字节数（至少16字节）的密钥
+/// EN: Generate a secret of the given number of bytes (at least 16) using the OS CSPRNG
+pub fn generate_secret_with_length(length: usize) -> Result<Secret, SecretError> {
+    if length < MIN_SECRET_LENGTH {
+        return Err(SecretError::InvalidLength);
+    }
+    let mut secret = Secret::from(vec![0u8; length]);
+    getrandom::fill(&mut secret.bytes).map_err(|_| SecretError::Random)?;
+    Ok(secret)
+}
@@ -0,0 +1,251 @@
+use crate::utils::i18n::message;
+use crate::utils::totp::totp::HashAlgorithm;
+use core::fmt;
+use core::str::FromStr;
+
+/// CN: 规范化后算法名称的最大长度
+/// EN: Maximum length of a normalized algorithm name
+const MAX_NAME_LEN: usize = 32;
+
+/// CN: 哈希算法解析错误类型枚举
+/// EN: Hash algorithm parsing error type enumeration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithmError {
+    /// CN: 输入为空
+    /// EN: Empty input
+    Empty,
+    /// CN: 无法识别的算法名称
+    /// EN: Unrecognized algorithm name
+    UnknownName,
+    /// CN: 无法识别的OID
+    /// EN: Unrecognized OID
+    UnknownOid,
+    /// CN: 已识别但不支持用于HMAC口令的算法，附带算法名称
+    /// EN: Recognized algorithm not supported for HMAC-based codes, with its name
+    Unsupported(&'static str),
+    /// CN: 算法需要启用的cargo特性，附带特性名称
+    /// EN: Algorithm requiring a cargo feature that is not enabled, with the feature name
+    FeatureDisabled(&'static str),
+}
+
+/// CN: 为HashAlgorithmError实现显示特征，提供错误信息的国际化支持
+/// EN: Implement Display trait for HashAlgorithmError with internationalization support
+impl fmt::Display for HashAlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashAlgorithmError::Empty => write!(f, "{}", message("algorithm.errors.empty")),
+            HashAlgorithmError::UnknownName => {
+                write!(f, "{}", message("algorithm.errors.unknown_name"))
+            }
+            HashAlgorithmError::UnknownOid => {
+                write!(f, "{}", message("algorithm.errors.unknown_oid"))
+            }
+            HashAlgorithmError::Unsupported(name) => {
+                write!(f, "{}: {}", message("algorithm.errors.unsupported"), name)
+            }
+            HashAlgorithmError::FeatureDisabled(feature) => write!(
+                f,
+                "{}: {}",
+                message("algorithm.errors.feature_disabled"),
+                feature
+            ),
+        }
+    }
+}
+
+/// CN: 从名称或OID解析哈希算法
+/// CN: 名称不区分大小写，忽略 `-`、`_`、`.`、`/` 与空格，并接受 `HMAC`、`HmacWith` 前缀，如 `SHA1`、`sha-256`、`HmacSHA512`、`SM3`、`SHA3-256`
+/// CN: OID为点分十进制，可带 `urn:oid:` 前缀，哈希算法OID与对应的HMAC算法OID（PSKC、PKCS#5等密钥容器所用）均可识别
+///
+/// EN: Parse a hash algorithm from a name or an OID
+/// EN: Names are case-insensitive, ignore `-`, `_`, `.`, `/` and spaces and accept an `HMAC` or `HmacWith` prefix,
+/// EN: e.g. `SHA1`, `sha-256`, `HmacSHA512`, `SM3`, `SHA3-256`
+/// EN: OIDs are dotted decimal, optionally prefixed with `urn:oid:`, and both the hash OID and the matching HMAC OID
+/// EN: (as used by PSKC, PKCS#5 and other key containers) are recognized
+///
+/// ```
+/// use totp_sm_rs::utils::totp::algorithm::HashAlgorithmError;
+/// use totp_sm_rs::utils::totp::totp::HashAlgorithm;
+///
+/// assert_eq!("sha-256".parse(), Ok(HashAlgorithm::SHA256));
+/// assert_eq!("HmacSHA512".parse(), Ok(HashAlgorithm::SHA512));
+/// assert_eq!(HashAlgorithm::try_from("1.2.840.113549.2.7"), Ok(HashAlgorithm::SHA1));
+/// assert_eq!(
+///     "MD5".parse::<HashAlgorithm>(),
+///     Err(HashAlgorithmError::Unsupported("MD5"))
+/// );
+/// ```
+impl FromStr for HashAlgorithm {
+    type Err = HashAlgorithmError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(HashAlgorithmError::Empty);
+        }
+        let oid = strip_prefix_ignore_case(input, "urn:oid:").unwrap_or(input);
+        if is_oid(oid) {
+            return from_oid(oid);
+        }
+
+        let mut buf = [0u8; MAX_NAME_LEN];
+        let name = normalize(input, &mut buf).ok_or(HashAlgorithmError::UnknownName)?;
+        // CN: `WITH` 只作为 `HmacWith` 前缀的一部分去除
+        // EN: `WITH` is only stripped as part of the `HmacWith` prefix
+        let name = match name.strip_prefix("HMAC") {
+            Some(rest) => rest.strip_prefix("WITH").unwrap_or(rest),
+            None => name,
+        };
+        from_name(name)
+    }
+}
+
+/// CN: 与FromStr相同，从名称或OID解析哈希算法
+/// EN: Same as FromStr, parsing a hash algorithm from a name or an OID
+impl TryFrom<&str> for HashAlgorithm {
+    type Error = HashAlgorithmError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+}
+
+/// CN: 将名称转为大写并去除分隔符，写入缓冲区，超出缓冲区长度时返回None
+/// EN: Uppercase the name and drop separators into the buffer, returning None when it does not fit
+fn normalize<'a>(input: &str, buf: &'a mut [u8; MAX_NAME_LEN]) -> Option<&'a str> {
+    let mut len = 0;
+    for byte in input.bytes() {
+        if matches!(byte, b'-' | b'_' | b'.' | b'/' | b' ') {
+            continue;
+        }
+        if !byte.is_ascii_alphanumeric() {
+            return None;
+        }
+        *buf.get_mut(len)? = byte.to_ascii_uppercase();
+        len += 1;
+    }
+    core::str::from_utf8(&buf[..len]).ok()
+}
+
+/// CN: 判断是否为点分十进制OID
+/// EN: Whether the input is a dotted decimal OID
+fn is_oid(input: &str) -> bool {
+    input.contains('.')
+        && input
+            .split('.')
+            .all(|arc| !arc.is_empty() && arc.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// CN: 忽略大小写去除前缀
+/// EN: Strip a prefix ignoring case
+fn strip_prefix_ignore_case<'a>(input: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = input.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &input[prefix.len()..])
+}
+
+/// CN: 由规范化后的名称查找哈希算法
+/// EN: Look up a hash algorithm by its normalized name
+fn from_name(name: &str) -> Result<HashAlgorithm, HashAlgorithmError> {
+    let algorithm = match name {
+        "SHA1" => HashAlgorithm::SHA1,
+        "SHA224" => HashAlgorithm::SHA224,
+        "SHA256" => HashAlgorithm::SHA256,
+        "SHA384" => HashAlgorithm::SHA384,
+        "SHA512" => HashAlgorithm::SHA512,
+        "SM3" => HashAlgorithm::SM3,
+        #[cfg(feature = "sha3")]
+        "SHA3224" => HashAlgorithm::SHA3_224,
+        #[cfg(feature = "sha3")]
+        "SHA3256" => HashAlgorithm::SHA3_256,
+        #[cfg(feature = "sha3")]
+        "SHA3384" => HashAlgorithm::SHA3_384,
+        #[cfg(feature = "sha3")]
+        "SHA3512" => HashAlgorithm::SHA3_512,
+        #[cfg(not(feature = "sha3"))]
+        "SHA3224" | "SHA3256" | "SHA3384" | "SHA3512" => {
+            return Err(HashAlgorithmError::FeatureDisabled("sha3"))
+        }
+        #[cfg(feature = "blake2")]
+        "BLAKE2B" | "BLAKE2B512" => HashAlgorithm::BLAKE2B,
+        #[cfg(feature = "blake2")]
+        "BLAKE2S" | "BLAKE2S256" => HashAlgorithm::BLAKE2S,
+        #[cfg(not(feature = "blake2"))]
+        "BLAKE2B" | "BLAKE2B512" | "BLAKE2S" | "BLAKE2S256" => {
+            return Err(HashAlgorithmError::FeatureDisabled("blake2"))
+        }
+        #[cfg(feature = "streebog")]
+        "STREEBOG256" | "GOST34112012256" | "GOSTR34112012256" => HashAlgorithm::STREEBOG256,
+        #[cfg(feature = "streebog")]
+        "STREEBOG512" | "GOST34112012512" | "GOSTR34112012512" => HashAlgorithm::STREEBOG512,
+        #[cfg(not(feature = "streebog"))]
+        "STREEBOG256" | "GOST34112012256" | "GOSTR34112012256" | "STREEBOG512"
+        | "GOST34112012512" | "GOSTR34112012512" => {
+            return Err(HashAlgorithmError::FeatureDisabled("streebog"))
+        }
+        "MD5" => return Err(HashAlgorithmError::Unsupported("MD5")),
+        "RIPEMD160" => return Err(HashAlgorithmError::Unsupported("RIPEMD160")),
+        "SHA512224" => return Err(HashAlgorithmError::Unsupported("SHA512/224")),
+        "SHA512256" => return Err(HashAlgorithmError::Unsupported("SHA512/256")),
+        _ => return Err(HashAlgorithmError::UnknownName),
+    };
+    Ok(algorithm)
+}
+
+/// CN: 由OID查找哈希算法，每种算法依次列出哈希算法OID与HMAC算法OID
+/// EN: Look up a hash algorithm by OID, listing the hash OID and then the HMAC OID of each algorithm
+fn from_oid(oid: &str) -> Result<HashAlgorithm, HashAlgorithmError> {
+    let algorithm = match oid {
+        "1.3.14.3.2.26" | "1.2.840.113549.2.7" => HashAlgorithm::SHA1,
+        "2.16.840.1.101.3.4.2.4" | "1.2.840.113549.2.8" => HashAlgorithm::SHA224,
+        "2.16.840.1.101.3.4.2.1" | "1.2.840.113549.2.9" => HashAlgorithm::SHA256,
+        "2.16.840.1.101.3.4.2.2" | "1.2.840.113549.2.10" => HashAlgorithm::SHA384,
+        "2.16.840.1.101.3.4.2.3" | "1.2.840.113549.2.11" => HashAlgorithm::SHA512,
+        "1.2.156.10197.1.401" | "1.2.156.10197.1.401.3.1" => HashAlgorithm::SM3,
+        #[cfg(feature = "sha3")]
+        "2.16.840.1.101.3.4.2.7" | "2.16.840.1.101.3.4.2.13" => HashAlgorithm::SHA3_224,
+        #[cfg(feature = "sha3")]
+        "2.16.840.1.101.3.4.2.8" | "2.16.840.1.101.3.4.2.14" => HashAlgorithm::SHA3_256,
+        #[cfg(feature = "sha3")]
+        "2.16.840.1.101.3.4.2.9" | "2.16.840.1.101.3.4.2.15" => HashAlgorithm::SHA3_384,
+        #[cfg(feature = "sha3")]
+        "2.16.840.1.101.3.4.2.10" | "2.16.840.1.101.3.4.2.16" => HashAlgorithm::SHA3_512,
+        #[cfg(not(feature = "sha3"))]
+        "2.16.840.1.101.3.4.2.7"
+        | "2.16.840.1.101.3.4.2.13"
+        | "2.16.840.1.101.3.4.2.8"
+        | "2.16.840.1.101.3.4.2.14"
+        | "2.16.840.1.101.3.4.2.9"
+        | "2.16.840.1.101.3.4.2.15"
+        | "2.16.840.1.101.3.4.2.10"
+        | "2.16.840.1.101.3.4.2.16" => return Err(HashAlgorithmError::FeatureDisabled("sha3")),
+        #[cfg(feature = "blake2")]
+        "1.3.6.1.4.1.1722.12.2.1.16" => HashAlgorithm::BLAKE2B,
+        #[cfg(feature = "blake2")]
+        "1.3.6.1.4.1.1722.12.2.2.8" => HashAlgorithm::BLAKE2S,
+        #[cfg(not(feature = "blake2"))]
+        "1.3.6.1.4.1.1722.12.2.1.16" | "1.3.6.1.4.1.1722.12.2.2.8" => {
+            return Err(HashAlgorithmError::FeatureDisabled("blake2"))
+        }
+        #[cfg(feature = "streebog")]
+        "1.2.643.7.1.1.2.2" | "1.2.643.7.1.1.4.1" => HashAlgorithm::STREEBOG256,
+        #[cfg(feature = "streebog")]
+        "1.2.643.7.1.1.2.3" | "1.2.643.7.1.1.4.2" => HashAlgorithm::STREEBOG512,
+        #[cfg(not(feature = "streebog"))]
+        "1.2.643.7.1.1.2.2" | "1.2.643.7.1.1.4.1" | "1.2.643.7.1.1.2.3" | "1.2.643.7.1.1.4.2" => {
+            return Err(HashAlgorithmError::FeatureDisabled("streebog"))
+        }
+        "1.2.840.113549.2.5" | "1.3.6.1.5.5.8.1.1" => {
+            return Err(HashAlgorithmError::Unsupported("MD5"))
+        }
+        "1.3.36.3.2.1" => return Err(HashAlgorithmError::Unsupported("RIPEMD160")),
+        "2.16.840.1.101.3.4.2.5" | "1.2.840.113549.2.12" => {
+            return Err(HashAlgorithmError::Unsupported("SHA512/224"))
+        }
+        "2.16.840.1.101.3.4.2.6" | "1.2.840.113549.2.13" => {
+            return Err(HashAlgorithmError::Unsupported("SHA512/256"))
+        }
+        _ => return Err(HashAlgorithmError::UnknownOid),
+    };
+    Ok(algorithm)
+}
@@ -0,0 +1,175 @@
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// CN: 时间源特征，返回自Unix纪元起经过的时间，生成与校验接口通过它获取当前时间
+/// EN: Time source trait returning the time elapsed since the Unix epoch, used by generation and verification to read the current time
+pub trait Clock {
+    /// CN: 获取当前时间（自Unix纪元起经过的时间）
+    /// EN: Get the current time (elapsed since the Unix epoch)
+    fn now(&self) -> Duration;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+/// CN: 系统时钟，读取操作系统的当前时间
+/// EN: System clock, reading the operating system's current time
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        // CN: 系统时间早于Unix纪元时按纪元处理
+        // EN: A system time before the Unix epoch is treated as the epoch
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+}
+
+/// CN: chrono的UTC时间作为固定时间源，早于Unix纪元时按纪元处理
+/// EN: A chrono UTC time as a fixed time source, treated as the epoch when before it
+#[cfg(feature = "chrono")]
+impl Clock for chrono::DateTime<chrono::Utc> {
+    fn now(&self) -> Duration {
+        Duration::new(
+            self.timestamp().max(0) as u64,
+            self.timestamp_subsec_nanos(),
+        )
+    }
+}
+
+/// CN: 固定时钟，始终返回同一时间
+/// EN: Fixed clock, always returning the same time
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock {
+    /// CN: 固定的时间
+    /// EN: Fixed time
+    time: Duration,
+}
+
+impl FixedClock {
+    pub fn new(time: Duration) -> Self {
+        Self { time }
+    }
+
+    /// CN: 以Unix时间（秒）创建固定时钟
+    /// EN: Create a fixed clock at a Unix time in seconds
+    pub fn from_unix(seconds: u64) -> Self {
+        Self::new(Duration::from_secs(seconds))
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> Duration {
+        self.time
+    }
+}
+
+/// CN: 偏移时钟，在另一个时钟的基础上加上或减去固定偏移，可用于校正已知的时钟偏差
+/// EN: Offset clock, adding or subtracting a fixed offset to another clock, e.g. to correct a known skew
+#[derive(Debug, Clone, Copy)]
+pub struct OffsetClock<C> {
+    /// CN: 基础时钟
+    /// EN: Underlying clock
+    inner: C,
+    /// CN: 偏移量
+    /// EN: Offset
+    offset: Duration,
+    /// CN: 偏移方向，true表示超前
+    /// EN: Offset direction, true meaning ahead
+    ahead: bool,
+}
+
+impl<C: Clock> OffsetClock<C> {
+    /// CN: 创建比基础时钟超前offset的时钟
+    /// EN: Create a clock running offset ahead of the underlying clock
+    pub fn ahead(inner: C, offset: Duration) -> Self {
+        Self {
+            inner,
+            offset,
+            ahead: true,
+        }
+    }
+
+    /// CN: 创建比基础时钟落后offset的时钟（不早于Unix纪元）
+    /// EN: Create a clock running offset behind the underlying clock (not before the Unix epoch)
+    pub fn behind(inner: C, offset: Duration) -> Self {
+        Self {
+            inner,
+            offset,
+            ahead: false,
+        }
+    }
+}
+
+impl<C: Clock> Clock for OffsetClock<C> {
+    fn now(&self) -> Duration {
+        let now = self.inner.now();
+        if self.ahead {
+            now.saturating_add(self.offset)
+        } else {
+            now.saturating_sub(self.offset)
+        }
+    }
+}
+
+/// CN: 可手动推进的模拟时钟，供测试快进时间窗口；可通过引用或Arc在多处共享
+/// EN: Manually advanced mock clock letting tests fast-forward through time windows; share it by reference or Arc
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct MockClock {
+    /// CN: 当前模拟时间
+    /// EN: Current mock time
+    time: Mutex<Duration>,
+}
+
+#[cfg(feature = "std")]
+impl MockClock {
+    pub fn new(time: Duration) -> Self {
+        Self {
+            time: Mutex::new(time),
+        }
+    }
+
+    /// CN: 以Unix时间（秒）创建模拟时钟
+    /// EN: Create a mock clock at a Unix time in seconds
+    pub fn from_unix(seconds: u64) -> Self {
+        Self::new(Duration::from_secs(seconds))
+    }
+
+    /// CN: 将时间向前推进
+    /// EN: Move the time forward
+    pub fn advance(&self, by: Duration) {
+        let mut time = self.time.lock().unwrap_or_else(|e| e.into_inner());
+        *time = time.saturating_add(by);
+    }
+
+    /// CN: 将时间设置为指定值
+    /// EN: Set the time to the given value
+    pub fn set(&self, time: Duration) {
+        *self.time.lock().unwrap_or_else(|e| e.into_inner()) = time;
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for MockClock {
+    fn now(&self) -> Duration {
+        *self.time.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
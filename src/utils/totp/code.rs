@@ -0,0 +1,179 @@
+use crate::utils::secret::secret::SecretSource;
+use crate::utils::totp::clock::{Clock, SystemClock};
+use crate::utils::totp::totp::{
+    compute_code, counter_at, current_time, TotpConfig, TotpError, NANOS_PER_SECOND,
+};
+use std::fmt;
+use std::time::Duration;
+
+/// CN: TOTP生成结果，包含验证码、时间步计数及其有效时间窗口
+/// EN: TOTP generation result, holding the code, its time-step counter and its validity window
+#[derive(Debug, Clone)]
+pub struct TotpCode {
+    /// CN: 验证码
+    /// EN: Verification code
+    code: String,
+    /// CN: 时间步计数
+    /// EN: Time-step counter
+    counter: i64,
+    /// CN: 生成时的时间（自Unix纪元起的纳秒数，已应用时区偏移）
+    /// EN: Time of generation (nanoseconds since the Unix epoch, timezone offset applied)
+    generated_at: i128,
+    /// CN: 生成时使用的配置，用于计算相邻时间步的验证码
+    /// EN: Configuration used for generation, reused for adjacent steps
+    config: TotpConfig,
+}
+
+impl TotpCode {
+    /// CN: 计算给定时间（纳秒，已应用时区偏移）所在时间步的验证码
+    /// EN: Compute the code of the step containing the given time (nanoseconds, timezone offset applied)
+    pub(crate) fn at_time(
+        secret_bytes: &[u8],
+        config: TotpConfig,
+        generated_at: i128,
+    ) -> Result<Self, TotpError> {
+        let counter = counter_at(&config, generated_at)?;
+        Ok(Self {
+            code: compute_code(secret_bytes, counter, &config)?,
+            counter,
+            generated_at,
+            config,
+        })
+    }
+
+    /// CN: 获取验证码
+    /// EN: Get the verification code
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// CN: 获取时间步计数
+    /// EN: Get the time-step counter
+    pub fn counter(&self) -> i64 {
+        self.counter
+    }
+
+    /// CN: 获取有效窗口的开始时间（自Unix纪元起，含）
+    /// EN: Get the start of the validity window (since the Unix epoch, inclusive)
+    pub fn valid_from(&self) -> Duration {
+        to_duration(self.window_start())
+    }
+
+    /// CN: 获取有效窗口的结束时间（自Unix纪元起，不含）
+    /// EN: Get the end of the validity window (since the Unix epoch, exclusive)
+    pub fn valid_until(&self) -> Duration {
+        to_duration(self.window_end())
+    }
+
+    /// CN: 获取生成时距离窗口结束的剩余时间，已过期的窗口返回0
+    /// EN: Get the time left in the window as of generation, zero for an expired window
+    pub fn remaining(&self) -> Duration {
+        to_duration(self.window_end() - self.generated_at)
+    }
+
+    /// CN: 获取向上取整的剩余秒数，适用于“验证码将在12秒后过期”之类的提示
+    /// EN: Get the remaining seconds rounded up, suited to prompts such as "code expires in 12s"
+    pub fn remaining_secs(&self) -> u64 {
+        let remaining = self.remaining();
+        remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0)
+    }
+
+    /// CN: 计算相对当前时间步偏移offset步的验证码，沿用相同的配置与生成时间
+    /// CN: 目标时间步早于T0或超出范围时返回InvalidTimestamp
+    ///
+    /// EN: Compute the code offset steps away from this one, reusing the same configuration and generation time
+    /// EN: Returns InvalidTimestamp when the target step is before T0 or out of range
+    pub fn at_offset<S: SecretSource + ?Sized>(
+        &self,
+        secret: &S,
+        offset: i64,
+    ) -> Result<Self, TotpError> {
+        let secret = secret.to_secret()?;
+        let counter = self
+            .counter
+            .checked_add(offset)
+            .filter(|counter| *counter >= 0)
+            .ok_or(TotpError::InvalidTimestamp)?;
+        Ok(Self {
+            code: compute_code(secret.as_bytes(), counter, &self.config)?,
+            counter,
+            ..self.clone()
+        })
+    }
+
+    /// CN: 计算下一个时间步的验证码
+    /// EN: Compute the code of the next time step
+    pub fn next<S: SecretSource + ?Sized>(&self, secret: &S) -> Result<Self, TotpError> {
+        self.at_offset(secret, 1)
+    }
+
+    /// CN: 计算上一个时间步的验证码
+    /// EN: Compute the code of the previous time step
+    pub fn previous<S: SecretSource + ?Sized>(&self, secret: &S) -> Result<Self, TotpError> {
+        self.at_offset(secret, -1)
+    }
+
+    /// CN: 窗口开始时间（纳秒）
+    /// EN: Window start in nanoseconds
+    fn window_start(&self) -> i128 {
+        self.config.t0 as i128 * NANOS_PER_SECOND
+            + self.counter as i128 * self.config.time_step.as_nanos() as i128
+    }
+
+    /// CN: 窗口结束时间（纳秒）
+    /// EN: Window end in nanoseconds
+    pub(crate) fn window_end(&self) -> i128 {
+        self.window_start() + self.config.time_step.as_nanos() as i128
+    }
+}
+
+impl fmt::Display for TotpCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+/// CN: 生成TOTP验证码及其有效时间窗口信息
+/// CN: 参数:
+/// CN: - secret: 密钥（Base32字符串或Secret）
+/// CN: - config: TOTP配置选项
+/// CN: 返回:
+/// CN: - Ok(TotpCode): 验证码、时间步计数、有效窗口与剩余时间
+/// CN: - Err(TotpError): 生成过程中的错误
+///
+/// EN: Generate a TOTP code together with its validity window
+/// EN: Parameters:
+/// EN: - secret: Secret key (Base32 string or Secret)
+/// EN: - config: TOTP configuration options
+/// EN: Returns:
+/// EN: - Ok(TotpCode): Code, time-step counter, validity window and remaining time
+/// EN: - Err(TotpError): Errors during generation
+pub fn generate_totp<S: SecretSource + ?Sized>(
+    secret: &S,
+    config: Option<TotpConfig>,
+) -> Result<TotpCode, TotpError> {
+    generate_totp_with_clock(secret, config, &SystemClock)
+}
+
+/// CN: 使用指定时间源生成TOTP验证码及其有效时间窗口信息，配置中的自定义时间戳优先于时间源
+/// EN: Generate a TOTP code with its validity window using the given time source, a custom timestamp in the configuration taking precedence over it
+pub fn generate_totp_with_clock<S: SecretSource + ?Sized, C: Clock + ?Sized>(
+    secret: &S,
+    config: Option<TotpConfig>,
+    clock: &C,
+) -> Result<TotpCode, TotpError> {
+    let config = config.unwrap_or_default();
+    config.validate()?;
+    let secret = secret.to_secret()?;
+    TotpCode::at_time(secret.as_bytes(), config, current_time(&config, clock)?)
+}
+
+/// CN: 将纳秒数转换为Duration，负数按0处理，超出范围时取最大值
+/// EN: Convert nanoseconds to a Duration, clamping negatives to zero and overflows to the maximum
+fn to_duration(nanos: i128) -> Duration {
+    if nanos <= 0 {
+        return Duration::ZERO;
+    }
+    let seconds = u64::try_from(nanos / NANOS_PER_SECOND).unwrap_or(u64::MAX);
+    Duration::new(seconds, (nanos % NANOS_PER_SECOND) as u32)
+}
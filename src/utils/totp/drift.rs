@@ -0,0 +1,189 @@
+use crate::utils::secret::secret::SecretSource;
+use crate::utils::totp::clock::{Clock, SystemClock};
+use crate::utils::totp::totp::{
+    compute_value, current_counter, match_window, window_steps, TotpConfig, TotpError,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// CN: 时钟漂移跟踪器，按凭据记录观测到的时间步漂移（RFC 6238 §6）
+/// CN: 每次校验都以已学习的漂移为中心展开窗口，并在校验成功后更新漂移估计
+///
+/// EN: Clock drift tracker, recording the observed time-step drift per credential (RFC 6238 §6)
+/// EN: Each verification centres its window on the learned drift and updates the estimate on success
+#[derive(Debug, Default)]
+pub struct DriftTracker {
+    /// CN: 凭据标识到漂移时间步数的映射
+    /// EN: Mapping from credential id to drift in time steps
+    drifts: Mutex<HashMap<String, i64>>,
+}
+
+impl DriftTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// CN: 获取凭据当前的漂移估计（时间步数，未记录时为0）
+    /// EN: Get the current drift estimate of a credential (in time steps, 0 when unknown)
+    pub fn drift(&self, credential_id: &str) -> i64 {
+        let drifts = self.drifts.lock().unwrap_or_else(|e| e.into_inner());
+        drifts.get(credential_id).copied().unwrap_or(0)
+    }
+
+    /// CN: 设置凭据的漂移估计，可用于从持久化存储中恢复
+    /// EN: Set the drift estimate of a credential, e.g. when restoring from persistent storage
+    pub fn set_drift(&self, credential_id: &str, drift: i64) {
+        let mut drifts = self.drifts.lock().unwrap_or_else(|e| e.into_inner());
+        drifts.insert(credential_id.to_string(), drift);
+    }
+
+    /// CN: 以已学习的漂移为中心校验TOTP验证码，成功后更新漂移估计
+    /// CN: 参数:
+    /// CN: - secret: 密钥（Base32字符串或Secret）
+    /// CN: - code: 待校验的验证码
+    /// CN: - config: TOTP配置选项
+    /// CN: - look_behind: 相对漂移中心向前回溯的时间步数
+    /// CN: - look_ahead: 相对漂移中心向后预读的时间步数
+    /// CN: - credential_id: 凭据标识
+    /// CN: 返回:
+    /// CN: - Ok(i64): 匹配成功的时间步相对当前时间的偏移量，即新的漂移估计
+    /// CN: - Err(TotpError): 验证码不匹配或校验过程中的错误
+    ///
+    /// EN: Verify a TOTP code in a window centred on the learned drift, updating the estimate on success
+    /// EN: Parameters:
+    /// EN: - secret: Secret key (Base32 string or Secret)
+    /// EN: - code: Code submitted for verification
+    /// EN: - config: TOTP configuration options
+    /// EN: - look_behind: Number of steps to accept before the drift centre
+    /// EN: - look_ahead: Number of steps to accept after the drift centre
+    /// EN: - credential_id: Credential identifier
+    /// EN: Returns:
+    /// EN: - Ok(i64): Offset of the matched step from the current time, i.e. the new drift estimate
+    /// EN: - Err(TotpError): Code mismatch or errors during verification
+    pub fn verify<S: SecretSource + ?Sized>(
+        &self,
+        secret: &S,
+        code: &str,
+        config: Option<TotpConfig>,
+        look_behind: u32,
+        look_ahead: u32,
+        credential_id: &str,
+    ) -> Result<i64, TotpError> {
+        self.verify_with_clock(
+            secret,
+            code,
+            config,
+            look_behind,
+            look_ahead,
+            credential_id,
+            &SystemClock,
+        )
+    }
+
+    /// CN: 使用指定时间源以已学习的漂移为中心校验TOTP验证码，配置中的自定义时间戳优先于时间源
+    /// EN: Verify a TOTP code centred on the learned drift using the given time source, a custom timestamp in the configuration taking precedence over it
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_clock<S: SecretSource + ?Sized, C: Clock + ?Sized>(
+        &self,
+        secret: &S,
+        code: &str,
+        config: Option<TotpConfig>,
+        look_behind: u32,
+        look_ahead: u32,
+        credential_id: &str,
+        clock: &C,
+    ) -> Result<i64, TotpError> {
+        let config = config.unwrap_or_default();
+        config.validate()?;
+        let secret = secret.to_secret()?;
+        let drift = self.drift(credential_id);
+        let centre = current_counter(&config, clock)?.saturating_add(drift);
+        let offset = match_window(code, centre, look_behind, look_ahead, None, |step| {
+            compute_value(secret.as_bytes(), step, &config)
+        })?;
+        let drift = drift.saturating_add(offset);
+        self.set_drift(credential_id, drift);
+        Ok(drift)
+    }
+
+    /// CN: 重新同步凭据的漂移：在较大的窗口内查找两个连续时间步的验证码
+    /// CN: 参数:
+    /// CN: - secret: 密钥（Base32字符串或Secret）
+    /// CN: - first_code: 第一个验证码
+    /// CN: - second_code: 紧随其后的下一个验证码
+    /// CN: - config: TOTP配置选项
+    /// CN: - max_drift: 相对当前时间允许搜索的最大时间步数
+    /// CN: - credential_id: 凭据标识
+    /// CN: 返回:
+    /// CN: - Ok(i64): 第二个验证码所在时间步相对当前时间的偏移量，即新的漂移估计
+    /// CN: - Err(TotpError): 未找到连续匹配或校验过程中的错误
+    ///
+    /// EN: Resynchronise a credential's drift by locating two consecutive codes within a wide window
+    /// EN: Parameters:
+    /// EN: - secret: Secret key (Base32 string or Secret)
+    /// EN: - first_code: First code
+    /// EN: - second_code: The code immediately following the first one
+    /// EN: - config: TOTP configuration options
+    /// EN: - max_drift: Maximum number of steps from the current time to search
+    /// EN: - credential_id: Credential identifier
+    /// EN: Returns:
+    /// EN: - Ok(i64): Offset of the second code's step from the current time, i.e. the new drift estimate
+    /// EN: - Err(TotpError): No consecutive match found or errors during verification
+    pub fn resync<S: SecretSource + ?Sized>(
+        &self,
+        secret: &S,
+        first_code: &str,
+        second_code: &str,
+        config: Option<TotpConfig>,
+        max_drift: u32,
+        credential_id: &str,
+    ) -> Result<i64, TotpError> {
+        self.resync_with_clock(
+            secret,
+            first_code,
+            second_code,
+            config,
+            max_drift,
+            credential_id,
+            &SystemClock,
+        )
+    }
+
+    /// CN: 使用指定时间源重新同步凭据的漂移，配置中的自定义时间戳优先于时间源
+    /// EN: Resynchronise a credential's drift using the given time source, a custom timestamp in the configuration taking precedence over it
+    #[allow(clippy::too_many_arguments)]
+    pub fn resync_with_clock<S: SecretSource + ?Sized, C: Clock + ?Sized>(
+        &self,
+        secret: &S,
+        first_code: &str,
+        second_code: &str,
+        config: Option<TotpConfig>,
+        max_drift: u32,
+        credential_id: &str,
+        clock: &C,
+    ) -> Result<i64, TotpError> {
+        let config = config.unwrap_or_default();
+        config.validate()?;
+        let secret = secret.to_secret()?;
+        let counter = current_counter(&config, clock)?;
+        // CN: 第二个验证码所在时间步不得超出max_drift
+        // EN: The second code's step must stay within max_drift
+        let Some(look_ahead) = max_drift.checked_sub(1) else {
+            return Err(TotpError::CodeMismatch);
+        };
+
+        // CN: 与常规校验一致，优先选择最接近当前时间的匹配
+        // EN: As with regular verification, prefer the match closest to the current time
+        for (offset, step) in window_steps(counter, max_drift, look_ahead) {
+            if !compute_value(secret.as_bytes(), step, &config)?.matches(first_code) {
+                continue;
+            }
+            if compute_value(secret.as_bytes(), step + 1, &config)?.matches(second_code) {
+                let drift = offset + 1;
+                self.set_drift(credential_id, drift);
+                return Ok(drift);
+            }
+        }
+        Err(TotpError::CodeMismatch)
+    }
+}
@@ -0,0 +1,232 @@
+use crate::utils::hotp::hotp::{truncate_value, OtpValue};
+#[cfg(feature = "std")]
+use crate::utils::secret::secret::SecretSource;
+use crate::utils::totp::clock::Clock;
+#[cfg(feature = "std")]
+use crate::utils::totp::clock::SystemClock;
+use crate::utils::totp::totp::{
+    current_counter, hash_algorithm_table, window_steps, HashAlgorithm, TotpConfig, TotpError,
+};
+use core::fmt;
+use hmac::digest::KeyInit;
+#[cfg(feature = "blake2")]
+use hmac::SimpleHmac;
+use hmac::{Hmac, Mac};
+
+/// CN: 由哈希算法对应表生成已载入密钥的HMAC实例，每个算法一个变体，与 `HashAlgorithm::visit_mac` 选用同一HMAC实现
+/// EN: Generate the keyed HMAC instance from the hash algorithm table, one variant per algorithm,
+/// EN: using the same HMAC implementation as `HashAlgorithm::visit_mac`
+macro_rules! keyed_mac {
+    ($($(#[$meta:meta])* $variant:ident => $name:literal, $digest:ty, $mac:ty;)*) => {
+        /// CN: 已载入密钥的HMAC实例，保存预先计算好的密钥状态
+        /// EN: Keyed HMAC instance holding the precomputed key state
+        #[derive(Clone)]
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+        enum KeyedMac {
+            $($(#[$meta])* $variant($mac),)*
+        }
+
+        impl KeyedMac {
+            fn new(secret_bytes: &[u8], hash_algorithm: HashAlgorithm) -> Result<Self, TotpError> {
+                let mac = match hash_algorithm {
+                    $($(#[$meta])* HashAlgorithm::$variant => Self::$variant(keyed(secret_bytes)?),)*
+                };
+                Ok(mac)
+            }
+
+            /// CN: 清零预先计算的密钥状态
+            /// EN: Wipe the precomputed key state
+            fn wipe(&mut self) {
+                match self {
+                    $($(#[$meta])* Self::$variant(mac) => {
+                        // SAFETY:
+                        // CN: Hmac、SimpleHmac及其摘要核心只由数组与整数组成，不含指针，全零是合法值；
+                        // CN: 其中唯一可能出现的Drop（sha3启用zeroize特性时的Sha3State）也只是清零自身数组
+                        // EN: Hmac, SimpleHmac and their digest cores consist only of arrays and integers with no pointers,
+                        // EN: so all zeroes is a valid value; the only Drop that can appear among them
+                        // EN: (Sha3State when sha3's zeroize feature is enabled) merely wipes its own array
+                        unsafe { zeroize::zeroize_flat_type(mac) }
+                    })*
+                }
+            }
+
+            /// CN: 复制密钥状态计算计数器的HMAC并动态截取，HMAC结果保存在栈上
+            /// EN: Compute the counter's HMAC from a copy of the key state and truncate it, keeping the HMAC result on the stack
+            fn truncated(&self, counter: u64, digits: u8) -> u32 {
+                let message = counter.to_be_bytes();
+                match self {
+                    $($(#[$meta])* Self::$variant(mac) => truncate_value(&finalize(mac, &message), digits),)*
+                }
+            }
+        }
+    };
+}
+hash_algorithm_table!(keyed_mac);
+
+/// CN: 以密钥创建HMAC实例
+/// EN: Create a keyed HMAC instance
+fn keyed<M: KeyInit>(secret_bytes: &[u8]) -> Result<M, TotpError> {
+    M::new_from_slice(secret_bytes).map_err(|_| TotpError::InvalidKeyLength)
+}
+
+/// CN: 在密钥状态的副本上计算消息的HMAC
+/// EN: Compute the HMAC of a message on a copy of the key state
+fn finalize<M: Mac + Clone>(mac: &M, message: &[u8]) -> hmac::digest::Output<M> {
+    let mut mac = mac.clone();
+    mac.update(message);
+    mac.finalize().into_bytes()
+}
+
+/// CN: 预先载入密钥的TOTP生成器，适用于高频生成与校验
+/// CN: 创建时一次性解码密钥并计算HMAC内外层密钥状态，此后按计数器生成验证码无需堆分配
+///
+/// EN: TOTP generator with a preloaded key, suited to high-rate generation and verification
+/// EN: The secret is decoded and the HMAC inner/outer key state computed once on creation,
+/// EN: after which codes for any counter are produced without heap allocation
+///
+/// ```
+/// use totp_sm_rs::utils::hotp::hotp::MAX_DIGITS;
+/// use totp_sm_rs::utils::totp::generator::TotpGenerator;
+/// use totp_sm_rs::utils::totp::totp::TotpConfig;
+///
+/// let config = TotpConfig {
+///     digits: 8,
+///     ..TotpConfig::default()
+/// };
+/// let generator = TotpGenerator::from_bytes(b"12345678901234567890", config).unwrap();
+/// assert_eq!(generator.code_value(1).value(), 94287082);
+/// let mut buf = [0u8; MAX_DIGITS];
+/// assert_eq!(generator.code_value(1).write_to(&mut buf).unwrap(), "94287082");
+/// ```
+#[derive(Clone)]
+pub struct TotpGenerator {
+    /// CN: 已载入密钥的HMAC实例
+    /// EN: Keyed HMAC instance
+    mac: KeyedMac,
+    /// CN: TOTP配置
+    /// EN: TOTP configuration
+    config: TotpConfig,
+}
+
+impl TotpGenerator {
+    /// CN: 校验配置、解码密钥并预先计算HMAC密钥状态
+    /// CN: 参数:
+    /// CN: - secret: 密钥（Base32字符串或Secret）
+    /// CN: - config: TOTP配置选项
+    /// CN: 返回:
+    /// CN: - Ok(TotpGenerator): 生成器
+    /// CN: - Err(TotpError): 配置或密钥无效
+    ///
+    /// EN: Validate the configuration, decode the secret and precompute the HMAC key state
+    /// EN: Parameters:
+    /// EN: - secret: Secret key (Base32 string or Secret)
+    /// EN: - config: TOTP configuration options
+    /// EN: Returns:
+    /// EN: - Ok(TotpGenerator): Generator
+    /// EN: - Err(TotpError): Invalid configuration or secret
+    #[cfg(feature = "std")]
+    pub fn new<S: SecretSource + ?Sized>(
+        secret: &S,
+        config: Option<TotpConfig>,
+    ) -> Result<Self, TotpError> {
+        let config = config.unwrap_or_default();
+        config.validate()?;
+        let secret = secret.to_secret()?;
+        Self::from_bytes(secret.as_bytes(), config)
+    }
+
+    /// CN: 由已解码的密钥字节创建生成器，不进行堆分配，可在no_std环境中使用
+    /// EN: Create a generator from decoded secret bytes without heap allocation, usable in no_std environments
+    pub fn from_bytes(secret_bytes: &[u8], config: TotpConfig) -> Result<Self, TotpError> {
+        config.validate()?;
+        // CN: 验证密钥长度（至少16字节，符合安全要求）
+        // EN: Validate key length (minimum 16 bytes for security)
+        if config.is_check_security && secret_bytes.len() < 16 {
+            return Err(TotpError::InvalidKeyLength);
+        }
+        Ok(Self {
+            mac: KeyedMac::new(secret_bytes, config.hash_algorithm)?,
+            config,
+        })
+    }
+
+    /// CN: 获取生成器使用的配置
+    /// EN: Get the configuration used by the generator
+    pub fn config(&self) -> &TotpConfig {
+        &self.config
+    }
+
+    /// CN: 计算时间步计数对应的验证码数值，不进行堆分配，可通过write_to写入调用方的缓冲区
+    /// EN: Compute the code value for a time-step counter without heap allocation, writable into a caller buffer via write_to
+    pub fn code_value(&self, counter: i64) -> OtpValue {
+        // CN: 时间步计数按其二进制补码作为HOTP计数器
+        // EN: The time-step counter is used as the HOTP counter via its two's complement bits
+        let digits = self.config.digits;
+        OtpValue::new(self.mac.truncated(counter as u64, digits), digits)
+    }
+
+    /// CN: 计算时间步计数对应的验证码字符串（补足前导零）
+    /// EN: Compute the code string for a time-step counter (zero-padded)
+    #[cfg(feature = "std")]
+    pub fn code_at(&self, counter: i64) -> String {
+        self.code_value(counter).to_string()
+    }
+
+    /// CN: 生成当前时间的验证码
+    /// EN: Generate the code for the current time
+    #[cfg(feature = "std")]
+    pub fn generate(&self) -> Result<String, TotpError> {
+        self.generate_with_clock(&SystemClock)
+    }
+
+    /// CN: 使用指定时间源生成验证码，配置中的自定义时间戳优先于时间源
+    /// EN: Generate a code using the given time source, a custom timestamp in the configuration taking precedence over it
+    #[cfg(feature = "std")]
+    pub fn generate_with_clock<C: Clock + ?Sized>(&self, clock: &C) -> Result<String, TotpError> {
+        Ok(self.code_at(current_counter(&self.config, clock)?))
+    }
+
+    /// CN: 校验验证码，允许前后若干个时间步长的偏差，比较过程不进行堆分配
+    /// CN: 返回匹配成功的时间步偏移量（负数表示过去，正数表示未来）
+    ///
+    /// EN: Verify a code tolerating a skew of several time steps, comparing without heap allocation
+    /// EN: Returns the matched time-step offset (negative is past, positive is future)
+    #[cfg(feature = "std")]
+    pub fn verify(&self, code: &str, look_behind: u32, look_ahead: u32) -> Result<i64, TotpError> {
+        self.verify_with_clock(code, look_behind, look_ahead, &SystemClock)
+    }
+
+    /// CN: 使用指定时间源校验验证码，配置中的自定义时间戳优先于时间源
+    /// EN: Verify a code using the given time source, a custom timestamp in the configuration taking precedence over it
+    pub fn verify_with_clock<C: Clock + ?Sized>(
+        &self,
+        code: &str,
+        look_behind: u32,
+        look_ahead: u32,
+        clock: &C,
+    ) -> Result<i64, TotpError> {
+        let counter = current_counter(&self.config, clock)?;
+        for (offset, step) in window_steps(counter, look_behind, look_ahead) {
+            if self.code_value(step).matches(code) {
+                return Ok(offset);
+            }
+        }
+        Err(TotpError::CodeMismatch)
+    }
+}
+
+/// CN: 释放时清零预先计算的HMAC密钥状态，该状态由密钥直接导出，泄露后即可伪造验证码
+/// EN: Wipe the precomputed HMAC key state on drop, as it is derived straight from the secret and enough to forge codes if leaked
+impl Drop for TotpGenerator {
+    fn drop(&mut self) {
+        self.mac.wipe();
+    }
+}
+
+impl fmt::Debug for TotpGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TotpGenerator")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
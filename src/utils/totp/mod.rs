@@ -1,2 +1,31 @@
+#[allow(clippy::module_inception)]
 pub mod totp;
-pub use totp::{generate_totp_code, TotpError};
+#[cfg(feature = "std")]
+pub use totp::{
+    generate_totp_code, generate_totp_code_with_clock, verify_totp, verify_totp_with_clock,
+};
+pub use totp::{totp_value, write_totp_code, TotpConfigBuilder, TotpError};
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "std")]
+pub use replay::{verify_totp_once, verify_totp_once_with_clock, InMemoryReplayStore, ReplayStore};
+pub mod algorithm;
+pub use algorithm::HashAlgorithmError;
+pub mod clock;
+pub use clock::{Clock, FixedClock, OffsetClock};
+#[cfg(feature = "std")]
+pub use clock::{MockClock, SystemClock};
+#[cfg(feature = "std")]
+pub mod code;
+#[cfg(feature = "std")]
+pub use code::{generate_totp, generate_totp_with_clock, TotpCode};
+#[cfg(feature = "tokio")]
+pub mod stream;
+#[cfg(feature = "tokio")]
+pub use stream::{totp_stream, totp_stream_with_clock, TotpStream};
+pub mod generator;
+pub use generator::TotpGenerator;
+#[cfg(feature = "std")]
+pub mod drift;
+#[cfg(feature = "std")]
+pub use drift::DriftTracker;
@@ -0,0 +1,140 @@
+use crate::utils::secret::secret::SecretSource;
+use crate::utils::totp::clock::{Clock, SystemClock};
+use crate::utils::totp::totp::{
+    compute_value, current_counter, match_window, TotpConfig, TotpError,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// CN: 重放防护存储特征，记录每个凭据最近一次被接受的时间步计数（RFC 6238 §5.2）
+/// EN: Replay protection store trait, recording the last accepted time-step counter per credential (RFC 6238 §5.2)
+pub trait ReplayStore {
+    /// CN: 获取凭据最近一次被接受的时间步计数
+    /// EN: Get the last accepted time-step counter of a credential
+    fn last_used_step(&self, credential_id: &str) -> Option<i64>;
+
+    /// CN: 记录凭据被接受的时间步计数
+    /// CN: 仅当step大于已记录的值时才更新并返回true，否则返回false；实现需保证该操作的原子性
+    ///
+    /// EN: Record an accepted time-step counter for a credential
+    /// EN: Only updates and returns true when step is greater than the stored value, otherwise returns false;
+    /// EN: implementations must perform this check-and-set atomically
+    fn mark_used_step(&self, credential_id: &str, step: i64) -> bool;
+}
+
+/// CN: 基于内存的重放防护存储，适用于单进程部署
+/// EN: In-memory replay protection store for single-process deployments
+#[derive(Debug, Default)]
+pub struct InMemoryReplayStore {
+    /// CN: 凭据标识到最近使用时间步计数的映射
+    /// EN: Mapping from credential id to last used time-step counter
+    steps: Mutex<HashMap<String, i64>>,
+}
+
+impl InMemoryReplayStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ReplayStore for InMemoryReplayStore {
+    fn last_used_step(&self, credential_id: &str) -> Option<i64> {
+        let steps = self.steps.lock().unwrap_or_else(|e| e.into_inner());
+        steps.get(credential_id).copied()
+    }
+
+    fn mark_used_step(&self, credential_id: &str, step: i64) -> bool {
+        let mut steps = self.steps.lock().unwrap_or_else(|e| e.into_inner());
+        match steps.get(credential_id) {
+            Some(&last) if step <= last => false,
+            _ => {
+                steps.insert(credential_id.to_string(), step);
+                true
+            }
+        }
+    }
+}
+
+/// CN: 校验TOTP验证码并防止重放，同一时间步（及更早的时间步）的验证码只能被接受一次
+/// CN: 参数:
+/// CN: - secret: 密钥（Base32字符串或Secret）
+/// CN: - code: 待校验的验证码
+/// CN: - config: TOTP配置选项
+/// CN: - look_behind: 向前回溯的时间步数
+/// CN: - look_ahead: 向后预读的时间步数
+/// CN: - credential_id: 凭据标识
+/// CN: - store: 重放防护存储
+/// CN: 返回:
+/// CN: - Ok(i64): 匹配成功的时间步偏移量
+/// CN: - Err(TotpError): 验证码不匹配、已被使用或校验过程中的错误
+///
+/// EN: Verify a TOTP code with replay protection, so a code for a given step (or any earlier step) is accepted only once
+/// EN: Parameters:
+/// EN: - secret: Secret key (Base32 string or Secret)
+/// EN: - code: Code submitted for verification
+/// EN: - config: TOTP configuration options
+/// EN: - look_behind: Number of past time steps to accept
+/// EN: - look_ahead: Number of future time steps to accept
+/// EN: - credential_id: Credential identifier
+/// EN: - store: Replay protection store
+/// EN: Returns:
+/// EN: - Ok(i64): Time-step offset that matched
+/// EN: - Err(TotpError): Code mismatch, replayed code or errors during verification
+pub fn verify_totp_once<K: SecretSource + ?Sized, S: ReplayStore + ?Sized>(
+    secret: &K,
+    code: &str,
+    config: Option<TotpConfig>,
+    look_behind: u32,
+    look_ahead: u32,
+    credential_id: &str,
+    store: &S,
+) -> Result<i64, TotpError> {
+    verify_totp_once_with_clock(
+        secret,
+        code,
+        config,
+        look_behind,
+        look_ahead,
+        credential_id,
+        store,
+        &SystemClock,
+    )
+}
+
+/// CN: 使用指定时间源校验TOTP验证码并防止重放，配置中的自定义时间戳优先于时间源
+/// EN: Verify a TOTP code with replay protection using the given time source, a custom timestamp in the configuration taking precedence over it
+#[allow(clippy::too_many_arguments)]
+pub fn verify_totp_once_with_clock<
+    K: SecretSource + ?Sized,
+    S: ReplayStore + ?Sized,
+    C: Clock + ?Sized,
+>(
+    secret: &K,
+    code: &str,
+    config: Option<TotpConfig>,
+    look_behind: u32,
+    look_ahead: u32,
+    credential_id: &str,
+    store: &S,
+    clock: &C,
+) -> Result<i64, TotpError> {
+    let config = config.unwrap_or_default();
+    config.validate()?;
+    let secret = secret.to_secret()?;
+    let counter = current_counter(&config, clock)?;
+    let offset = match_window(
+        code,
+        counter,
+        look_behind,
+        look_ahead,
+        store.last_used_step(credential_id),
+        |step| compute_value(secret.as_bytes(), step, &config),
+    )?;
+
+    // CN: 记录失败说明并发请求已抢先使用了该时间步
+    // EN: A failed record means a concurrent request already consumed this step
+    if !store.mark_used_step(credential_id, counter.saturating_add(offset)) {
+        return Err(TotpError::ReplayedCode);
+    }
+    Ok(offset)
+}
@@ -0,0 +1,116 @@
+use crate::utils::secret::secret::{Secret, SecretSource};
+use crate::utils::totp::clock::{Clock, SystemClock};
+use crate::utils::totp::code::TotpCode;
+use crate::utils::totp::totp::{current_time, TotpConfig, TotpError};
+use futures_core::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::time::{Instant, Sleep};
+
+/// CN: 按时间步边界产出验证码的异步流
+/// CN: 首次轮询立即产出当前时间步的验证码，此后在每个新时间步开始时产出一项；出错时产出该错误并结束
+///
+/// EN: Async stream yielding codes at time-step boundaries
+/// EN: The first poll yields the current step's code at once, then one item as each new step begins; an error is yielded once and ends the stream
+pub struct TotpStream<C> {
+    /// CN: 密钥
+    /// EN: Secret key
+    secret: Secret,
+    /// CN: TOTP配置（忽略自定义时间戳）
+    /// EN: TOTP configuration (custom timestamp ignored)
+    config: TotpConfig,
+    /// CN: 时间源
+    /// EN: Time source
+    clock: C,
+    /// CN: 最近产出的时间步计数
+    /// EN: Time-step counter of the last item
+    last_counter: Option<i64>,
+    /// CN: 等待下一个时间步的定时器，首次等待时创建
+    /// EN: Timer waiting for the next step, created on first wait
+    sleep: Option<Pin<Box<Sleep>>>,
+    /// CN: 是否已结束
+    /// EN: Whether the stream has ended
+    finished: bool,
+}
+
+impl<C: Clock + Unpin> Stream for TotpStream<C> {
+    type Item = Result<TotpCode, TotpError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.finished {
+            return Poll::Ready(None);
+        }
+        loop {
+            let code = match current_time(&this.config, &this.clock)
+                .and_then(|now| TotpCode::at_time(this.secret.as_bytes(), this.config, now))
+            {
+                Ok(code) => code,
+                Err(e) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            };
+            if this.last_counter.is_none_or(|last| code.counter() > last) {
+                this.last_counter = Some(code.counter());
+                return Poll::Ready(Some(Ok(code)));
+            }
+
+            // CN: 睡眠到时间源报告的窗口结束，醒来后以时间源为准重新检查，时间源走得慢时会继续等待
+            // EN: Sleep until the window ends according to the clock, then re-check against the clock, waiting again if it runs slow
+            let deadline = Instant::now() + code.remaining();
+            let sleep = this
+                .sleep
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+            sleep.as_mut().reset(deadline);
+            if sleep.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+/// CN: 创建使用系统时钟、按时间步边界产出验证码的异步流
+/// CN: 参数:
+/// CN: - secret: 密钥（Base32字符串或Secret）
+/// CN: - config: TOTP配置选项，其中的自定义时间戳被忽略
+/// CN: 返回:
+/// CN: - Ok(TotpStream): 验证码流，需在Tokio运行时中轮询
+/// CN: - Err(TotpError): 配置或密钥无效
+///
+/// EN: Create an async stream yielding codes at time-step boundaries using the system clock
+/// EN: Parameters:
+/// EN: - secret: Secret key (Base32 string or Secret)
+/// EN: - config: TOTP configuration options, whose custom timestamp is ignored
+/// EN: Returns:
+/// EN: - Ok(TotpStream): Code stream, to be polled within a Tokio runtime
+/// EN: - Err(TotpError): Invalid configuration or secret
+pub fn totp_stream<S: SecretSource + ?Sized>(
+    secret: &S,
+    config: Option<TotpConfig>,
+) -> Result<TotpStream<SystemClock>, TotpError> {
+    totp_stream_with_clock(secret, config, SystemClock)
+}
+
+/// CN: 创建使用指定时间源、按时间步边界产出验证码的异步流，配置中的自定义时间戳被忽略
+/// EN: Create an async stream yielding codes at time-step boundaries using the given time source, ignoring the configuration's custom timestamp
+pub fn totp_stream_with_clock<S: SecretSource + ?Sized, C: Clock>(
+    secret: &S,
+    config: Option<TotpConfig>,
+    clock: C,
+) -> Result<TotpStream<C>, TotpError> {
+    let config = TotpConfig {
+        timestamp: None,
+        ..config.unwrap_or_default()
+    };
+    config.validate()?;
+    Ok(TotpStream {
+        secret: secret.to_secret()?,
+        config,
+        clock,
+        last_counter: None,
+        sleep: None,
+        finished: false,
+    })
+}
@@ -1,31 +1,25 @@
-use crate::utils::i18n::I18n;
-use base32::Alphabet::Rfc4648;
-use chrono::Utc;
+use crate::utils::hotp::hotp::{compute_hotp_value, digest_value, OtpValue};
+use crate::utils::i18n::message;
+#[cfg(feature = "std")]
+use crate::utils::secret::secret::SecretSource;
+use crate::utils::totp::clock::Clock;
+#[cfg(feature = "std")]
+use crate::utils::totp::clock::SystemClock;
+use core::fmt;
+use core::time::Duration;
+use hmac::digest::core_api::BlockSizeUser;
+use hmac::digest::{Digest, KeyInit};
+#[cfg(feature = "blake2")]
+use hmac::SimpleHmac;
 use hmac::{Hmac, Mac};
-use sha1::Sha1;
-use sha2::{Sha256, Sha512};
-use sm3::Sm3;
-use std::fmt;
-
-/// CN: HMAC-SHA1 类型别名
-/// EN: Type alias for HMAC-SHA1
-type HmacSha1 = Hmac<Sha1>;
-
-/// CN: HMAC-SHA256 类型别名
-/// EN: Type alias for HMAC-SHA256
-type HmacSha256 = Hmac<Sha256>;
-
-/// CN: HMAC-SHA512 类型别名
-/// EN: Type alias for HMAC-SHA512
-type HmacSha512 = Hmac<Sha512>;
-
-/// CN: HMAC-SM3 类型别名
-/// EN: Type alias for HMAC-SM3
-type HmacSm3 = Hmac<Sm3>;
 
 /// CN: 哈希算法枚举，支持多种哈希算法实现
+/// CN: SHA3、BLAKE2与Streebog分别由sha3、blake2、streebog特性启用，因此该枚举不可穷尽匹配
+///
 /// EN: Hash algorithm enumeration, supporting multiple hash algorithm implementations
-#[derive(Debug, Clone, Copy)]
+/// EN: SHA3, BLAKE2 and Streebog are enabled by the sha3, blake2 and streebog features, so the enumeration is non-exhaustive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum HashAlgorithm {
     /// CN: SHA1 哈希算法（RFC 4226标准）
     /// EN: SHA1 hash algorithm (RFC 4226 standard)
@@ -39,16 +33,167 @@ pub enum HashAlgorithm {
     /// CN: 国密SM3哈希算法
     /// EN: Chinese SM3 hash algorithm
     SM3,
+    /// CN: SHA224 哈希算法
+    /// EN: SHA224 hash algorithm
+    SHA224,
+    /// CN: SHA384 哈希算法
+    /// EN: SHA384 hash algorithm
+    SHA384,
+    /// CN: SHA3-224 哈希算法
+    /// EN: SHA3-224 hash algorithm
+    #[cfg(feature = "sha3")]
+    SHA3_224,
+    /// CN: SHA3-256 哈希算法
+    /// EN: SHA3-256 hash algorithm
+    #[cfg(feature = "sha3")]
+    SHA3_256,
+    /// CN: SHA3-384 哈希算法
+    /// EN: SHA3-384 hash algorithm
+    #[cfg(feature = "sha3")]
+    SHA3_384,
+    /// CN: SHA3-512 哈希算法
+    /// EN: SHA3-512 hash algorithm
+    #[cfg(feature = "sha3")]
+    SHA3_512,
+    /// CN: BLAKE2b 哈希算法（512位输出）
+    /// EN: BLAKE2b hash algorithm (512-bit output)
+    #[cfg(feature = "blake2")]
+    BLAKE2B,
+    /// CN: BLAKE2s 哈希算法（256位输出）
+    /// EN: BLAKE2s hash algorithm (256-bit output)
+    #[cfg(feature = "blake2")]
+    BLAKE2S,
+    /// CN: GOST R 34.11-2012 Streebog 哈希算法（256位输出）
+    /// EN: GOST R 34.11-2012 Streebog hash algorithm (256-bit output)
+    #[cfg(feature = "streebog")]
+    STREEBOG256,
+    /// CN: GOST R 34.11-2012 Streebog 哈希算法（512位输出）
+    /// EN: GOST R 34.11-2012 Streebog hash algorithm (512-bit output)
+    #[cfg(feature = "streebog")]
+    STREEBOG512,
+}
+
+/// CN: 按哈希算法选择的摘要实现的访问者，使各调用处只需编写一次泛型逻辑
+/// EN: Visitor over the digest selected by a hash algorithm, letting each caller write its generic logic once
+pub(crate) trait DigestVisitor {
+    /// CN: 访问结果
+    /// EN: Visit result
+    type Output;
+
+    /// CN: 使用摘要算法D执行
+    /// EN: Run with digest D
+    fn visit<D: Digest + BlockSizeUser + Clone>(self) -> Self::Output;
+}
+
+/// CN: 按哈希算法选择的HMAC实现的访问者
+/// CN: 急切缓冲的摘要（SHA-1/2、SM3、SHA-3、Streebog）使用预先计算内外层状态的 `Hmac`，其余（BLAKE2）使用 `SimpleHmac`
+///
+/// EN: Visitor over the HMAC implementation selected by a hash algorithm
+/// EN: Eager-buffered digests (SHA-1/2, SM3, SHA-3, Streebog) use `Hmac` with precomputed inner and outer state,
+/// EN: the rest (BLAKE2) use `SimpleHmac`
+pub(crate) trait MacVisitor {
+    /// CN: 访问结果
+    /// EN: Visit result
+    type Output;
+
+    /// CN: 使用HMAC实现M执行
+    /// EN: Run with HMAC implementation M
+    fn visit<M: Mac + KeyInit + Clone>(self) -> Self::Output;
+}
+
+/// CN: 哈希算法与名称、摘要实现及HMAC实现的对应表，以每行一个算法的形式交给回调宏
+/// CN: `HashAlgorithm` 的 `ALL`、`name`、`visit`、`visit_mac` 以及 `TotpGenerator` 的密钥状态均由此表生成，新增算法只需在此添加一行
+///
+/// EN: Table mapping each hash algorithm to its name, digest and HMAC implementation, handed row by row to a callback macro
+/// EN: `HashAlgorithm`'s `ALL`, `name`, `visit` and `visit_mac` and the `TotpGenerator` key state are all generated from it,
+/// EN: so adding an algorithm only takes a new row here
+macro_rules! hash_algorithm_table {
+    ($callback:ident) => {
+        $callback! {
+            SHA1 => "SHA1", sha1::Sha1, Hmac<sha1::Sha1>;
+            SHA256 => "SHA256", sha2::Sha256, Hmac<sha2::Sha256>;
+            SHA512 => "SHA512", sha2::Sha512, Hmac<sha2::Sha512>;
+            SM3 => "SM3", sm3::Sm3, Hmac<sm3::Sm3>;
+            SHA224 => "SHA224", sha2::Sha224, Hmac<sha2::Sha224>;
+            SHA384 => "SHA384", sha2::Sha384, Hmac<sha2::Sha384>;
+            #[cfg(feature = "sha3")]
+            SHA3_224 => "SHA3-224", sha3::Sha3_224, Hmac<sha3::Sha3_224>;
+            #[cfg(feature = "sha3")]
+            SHA3_256 => "SHA3-256", sha3::Sha3_256, Hmac<sha3::Sha3_256>;
+            #[cfg(feature = "sha3")]
+            SHA3_384 => "SHA3-384", sha3::Sha3_384, Hmac<sha3::Sha3_384>;
+            #[cfg(feature = "sha3")]
+            SHA3_512 => "SHA3-512", sha3::Sha3_512, Hmac<sha3::Sha3_512>;
+            // CN: BLAKE2为惰性缓冲，Hmac不适用
+            // EN: BLAKE2 is lazily buffered, which Hmac does not support
+            #[cfg(feature = "blake2")]
+            BLAKE2B => "BLAKE2B", blake2::Blake2b512, SimpleHmac<blake2::Blake2b512>;
+            #[cfg(feature = "blake2")]
+            BLAKE2S => "BLAKE2S", blake2::Blake2s256, SimpleHmac<blake2::Blake2s256>;
+            #[cfg(feature = "streebog")]
+            STREEBOG256 => "STREEBOG256", streebog::Streebog256, Hmac<streebog::Streebog256>;
+            #[cfg(feature = "streebog")]
+            STREEBOG512 => "STREEBOG512", streebog::Streebog512, Hmac<streebog::Streebog512>;
+        }
+    };
+}
+pub(crate) use hash_algorithm_table;
+
+/// CN: 由对应表生成HashAlgorithm的算法列表、名称与访问者分发
+/// EN: Generate HashAlgorithm's algorithm list, names and visitor dispatch from the table
+macro_rules! impl_hash_algorithm {
+    ($($(#[$meta:meta])* $variant:ident => $name:literal, $digest:ty, $mac:ty;)*) => {
+        impl HashAlgorithm {
+            /// CN: 当前构建启用的全部哈希算法
+            /// EN: Every hash algorithm enabled in the current build
+            pub const ALL: &'static [HashAlgorithm] = &[$($(#[$meta])* HashAlgorithm::$variant,)*];
+
+            /// CN: 获取算法名称，与otpauth URI中algorithm参数的写法一致
+            /// EN: Get the algorithm name, spelled as in the otpauth URI algorithm parameter
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($(#[$meta])* HashAlgorithm::$variant => $name,)*
+                }
+            }
+
+            /// CN: 以该算法对应的摘要实现执行访问者
+            /// EN: Run the visitor with the digest implementing this algorithm
+            pub(crate) fn visit<V: DigestVisitor>(self, visitor: V) -> V::Output {
+                match self {
+                    $($(#[$meta])* HashAlgorithm::$variant => visitor.visit::<$digest>(),)*
+                }
+            }
+
+            /// CN: 以该算法对应的HMAC实现执行访问者
+            /// EN: Run the visitor with the HMAC implementation for this algorithm
+            pub(crate) fn visit_mac<V: MacVisitor>(self, visitor: V) -> V::Output {
+                match self {
+                    $($(#[$meta])* HashAlgorithm::$variant => visitor.visit::<$mac>(),)*
+                }
+            }
+        }
+    };
+}
+hash_algorithm_table!(impl_hash_algorithm);
+
+impl HashAlgorithm {
+    /// CN: 获取摘要（即HMAC输出）长度（字节）
+    /// EN: Get the digest (and so HMAC output) length in bytes
+    pub fn output_size(&self) -> usize {
+        struct OutputSize;
+        impl DigestVisitor for OutputSize {
+            type Output = usize;
+            fn visit<D: Digest + BlockSizeUser + Clone>(self) -> usize {
+                <D as Digest>::output_size()
+            }
+        }
+        self.visit(OutputSize)
+    }
 }
 
 impl fmt::Display for HashAlgorithm {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            HashAlgorithm::SHA1 => write!(f, "SHA1"),
-            HashAlgorithm::SHA256 => write!(f, "SHA256"),
-            HashAlgorithm::SHA512 => write!(f, "SHA512"),
-            HashAlgorithm::SM3 => write!(f, "SM3"),
-        }
+        f.write_str(self.name())
     }
 }
 
@@ -60,6 +205,10 @@ impl Default for HashAlgorithm {
     }
 }
 
+/// CN: 每秒的纳秒数
+/// EN: Nanoseconds per second
+pub(crate) const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
 /// CN: TOTP配置结构体，用于自定义TOTP生成参数
 /// EN: TOTP configuration struct for customizing TOTP generation parameters
 #[derive(Debug, Copy, Clone)]
@@ -67,9 +216,12 @@ pub struct TotpConfig {
     /// CN: 验证码位数（4至10位）
     /// EN: Number of digits in the verification code （4 to 10 digits）
     pub digits: u8,
-    /// CN: 时间步长（秒）
-    /// EN: Time step in seconds
-    pub time_step: u8,
+    /// CN: 时间步长，支持小时、天等长周期，也支持便于测试的亚秒级步长
+    /// EN: Time step, allowing long periods such as hours or days as well as sub-second steps for testing
+    pub time_step: Duration,
+    /// CN: 开始计数的Unix时间（秒，RFC 6238 中的T0）
+    /// EN: Unix time in seconds to start counting steps from (T0 in RFC 6238)
+    pub t0: i64,
     /// CN: 可选的自定义时间戳
     /// EN: Optional custom timestamp
     pub timestamp: Option<i64>,
@@ -91,7 +243,8 @@ impl Default for TotpConfig {
     fn default() -> Self {
         Self {
             digits: 6,
-            time_step: 30,
+            time_step: Duration::from_secs(30),
+            t0: 0,
             timestamp: None,
             timezone_offset: None,
             hash_algorithm: HashAlgorithm::default(),
@@ -100,6 +253,107 @@ impl Default for TotpConfig {
     }
 }
 
+impl TotpConfig {
+    /// CN: 创建以默认配置为起点的构建器
+    /// EN: Create a builder starting from the default configuration
+    pub fn builder() -> TotpConfigBuilder {
+        TotpConfigBuilder::default()
+    }
+
+    /// CN: 校验配置：位数须为4至10位，时间步长须大于0，自定义时间戳不得早于T0
+    /// EN: Validate the configuration: 4 to 10 digits, a non-zero time step and a custom timestamp not before T0
+    pub fn validate(&self) -> Result<(), TotpError> {
+        check_digits(self.digits)?;
+        if self.time_step.is_zero() {
+            return Err(TotpError::InvalidTimeStep);
+        }
+        if self.timestamp.is_some_and(|ts| ts < self.t0) {
+            return Err(TotpError::InvalidTimestamp);
+        }
+        Ok(())
+    }
+}
+
+/// CN: TOTP配置构建器，构建时统一校验全部字段
+/// EN: TOTP configuration builder, validating every field when building
+///
+/// ```
+/// use std::time::Duration;
+/// use totp_sm_rs::utils::totp::totp::{HashAlgorithm, TotpConfig};
+///
+/// let config = TotpConfig::builder()
+///     .digits(8)
+///     .time_step(Duration::from_secs(60))
+///     .hash_algorithm(HashAlgorithm::SHA256)
+///     .build()
+///     .unwrap();
+/// assert_eq!(config.digits, 8);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TotpConfigBuilder {
+    /// CN: 构建中的配置
+    /// EN: Configuration being built
+    config: TotpConfig,
+}
+
+impl TotpConfigBuilder {
+    /// CN: 设置验证码位数（4至10位）
+    /// EN: Set the number of code digits (4 to 10)
+    pub fn digits(mut self, digits: u8) -> Self {
+        self.config.digits = digits;
+        self
+    }
+
+    /// CN: 设置时间步长（须大于0）
+    /// EN: Set the time step (must be non-zero)
+    pub fn time_step(mut self, time_step: Duration) -> Self {
+        self.config.time_step = time_step;
+        self
+    }
+
+    /// CN: 设置开始计数的Unix时间（秒，RFC 6238 中的T0）
+    /// EN: Set the Unix time in seconds to start counting steps from (T0 in RFC 6238)
+    pub fn t0(mut self, t0: i64) -> Self {
+        self.config.t0 = t0;
+        self
+    }
+
+    /// CN: 设置自定义时间戳（Unix秒）
+    /// EN: Set a custom timestamp (Unix seconds)
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.config.timestamp = Some(timestamp);
+        self
+    }
+
+    /// CN: 设置时区偏移（秒）
+    /// EN: Set the timezone offset in seconds
+    pub fn timezone_offset(mut self, offset: i64) -> Self {
+        self.config.timezone_offset = Some(offset);
+        self
+    }
+
+    /// CN: 设置哈希算法
+    /// EN: Set the hash algorithm
+    pub fn hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> Self {
+        self.config.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    /// CN: 设置是否检查密钥长度安全性
+    /// EN: Set whether to check the secret length for security
+    pub fn check_security(mut self, check: bool) -> Self {
+        self.config.is_check_security = check;
+        self
+    }
+
+    /// CN: 校验并生成配置
+    /// EN: Validate and produce the configuration
+    pub fn build(self) -> Result<TotpConfig, TotpError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
 /// CN: TOTP错误类型枚举
 /// EN: TOTP error type enumeration
 #[derive(Debug)]
@@ -110,31 +364,66 @@ pub enum TotpError {
     /// CN: 密钥长度无效
     /// EN: Invalid key length
     InvalidKeyLength,
+    /// CN: 验证码不匹配
+    /// EN: Code does not match
+    CodeMismatch,
+    /// CN: 验证码已被使用（重放）
+    /// EN: Code has already been used (replayed)
+    ReplayedCode,
+    /// CN: 动态口令因子无效
+    /// EN: Invalid dynamic password factor
+    InvalidFactor,
+    /// CN: 验证码位数无效（须为4至10位）
+    /// EN: Invalid number of code digits (must be 4 to 10)
+    InvalidDigits,
+    /// CN: 时间步长无效（须大于0）
+    /// EN: Invalid time step (must be non-zero)
+    InvalidTimeStep,
+    /// CN: 时间戳无效（早于T0）
+    /// EN: Invalid timestamp (before T0)
+    InvalidTimestamp,
+    /// CN: 输出缓冲区过小，无法写入全部验证码位数
+    /// EN: Output buffer too small to hold every code digit
+    BufferTooSmall,
+    /// CN: 摘要算法的输出长度不足20字节，无法进行动态截取
+    /// EN: Digest output shorter than 20 bytes, too short for dynamic truncation
+    UnsupportedDigest,
+    /// CN: HOTP计数器已达上限，无法再推进
+    /// EN: HOTP counter exhausted and cannot advance any further
+    CounterExhausted,
 }
 
 /// CN: 为TotpError实现显示特征，提供错误信息的国际化支持
 /// EN: Implement Display trait for TotpError with internationalization support
 impl fmt::Display for TotpError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let i18n = I18n::new();
         match self {
-            TotpError::Base32DecodeError => write!(
-                f,
-                "{}",
-                i18n.get_message("totp.errors.base32_decode").unwrap()
-            ),
-            TotpError::InvalidKeyLength => write!(
-                f,
-                "{}",
-                i18n.get_message("totp.errors.invalid_key_length").unwrap()
-            ),
+            TotpError::Base32DecodeError => write!(f, "{}", message("totp.errors.base32_decode")),
+            TotpError::InvalidKeyLength => {
+                write!(f, "{}", message("totp.errors.invalid_key_length"))
+            }
+            TotpError::CodeMismatch => write!(f, "{}", message("totp.errors.code_mismatch")),
+            TotpError::ReplayedCode => write!(f, "{}", message("totp.errors.replayed_code")),
+            TotpError::InvalidFactor => write!(f, "{}", message("totp.errors.invalid_factor")),
+            TotpError::InvalidDigits => write!(f, "{}", message("totp.errors.invalid_digits")),
+            TotpError::InvalidTimeStep => write!(f, "{}", message("totp.errors.invalid_time_step")),
+            TotpError::InvalidTimestamp => {
+                write!(f, "{}", message("totp.errors.invalid_timestamp"))
+            }
+            TotpError::BufferTooSmall => write!(f, "{}", message("totp.errors.buffer_too_small")),
+            TotpError::UnsupportedDigest => {
+                write!(f, "{}", message("totp.errors.unsupported_digest"))
+            }
+            TotpError::CounterExhausted => {
+                write!(f, "{}", message("totp.errors.counter_exhausted"))
+            }
         }
     }
 }
 
 /// CN: 生成TOTP验证码
 /// CN: 参数:
-/// CN: - secret: Base32编码的密钥
+/// CN: - secret: 密钥（Base32字符串或Secret）
 /// CN: - config: TOTP配置选项，包含验证码位数、时间步长等参数
 /// CN: 返回:
 /// CN: - Ok(String): 成功生成的TOTP验证码
@@ -142,88 +431,346 @@ impl fmt::Display for TotpError {
 ///
 /// EN: Generate TOTP verification code
 /// EN: Parameters:
-/// EN: - secret: Base32 encoded secret key
+/// EN: - secret: Secret key (Base32 string or Secret)
 /// EN: - config: TOTP configuration options, including code digits, time step, etc.
 /// EN: Returns:
 /// EN: - Ok(String): Successfully generated TOTP code
 /// EN: - Err(TotpError): Errors during generation
-pub fn generate_totp_code(secret: &str, config: Option<TotpConfig>) -> Result<String, TotpError> {
+#[cfg(feature = "std")]
+pub fn generate_totp_code<S: SecretSource + ?Sized>(
+    secret: &S,
+    config: Option<TotpConfig>,
+) -> Result<String, TotpError> {
+    generate_totp_code_with_clock(secret, config, &SystemClock)
+}
+
+/// CN: 使用指定时间源生成TOTP验证码，配置中的自定义时间戳优先于时间源
+/// EN: Generate a TOTP code using the given time source, a custom timestamp in the configuration taking precedence over it
+#[cfg(feature = "std")]
+pub fn generate_totp_code_with_clock<S: SecretSource + ?Sized, C: Clock + ?Sized>(
+    secret: &S,
+    config: Option<TotpConfig>,
+    clock: &C,
+) -> Result<String, TotpError> {
     let config = config.unwrap_or_default();
-    if config.digits < 4 && config.digits > 10 {
-        return Err(TotpError::InvalidKeyLength);
+    config.validate()?;
+    let secret = secret.to_secret()?;
+    compute_code(secret.as_bytes(), current_counter(&config, clock)?, &config)
+}
+
+/// CN: 校验TOTP验证码，允许前后若干个时间步长的偏差
+/// CN: 参数:
+/// CN: - secret: 密钥（Base32字符串或Secret）
+/// CN: - code: 待校验的验证码
+/// CN: - config: TOTP配置选项
+/// CN: - look_behind: 向前回溯的时间步数
+/// CN: - look_ahead: 向后预读的时间步数
+/// CN: 返回:
+/// CN: - Ok(i64): 匹配成功的时间步偏移量（负数表示过去，正数表示未来）
+/// CN: - Err(TotpError): 验证码不匹配或校验过程中的错误
+///
+/// EN: Verify a TOTP code, tolerating a skew of several time steps
+/// EN: Parameters:
+/// EN: - secret: Secret key (Base32 string or Secret)
+/// EN: - code: Code submitted for verification
+/// EN: - config: TOTP configuration options
+/// EN: - look_behind: Number of past time steps to accept
+/// EN: - look_ahead: Number of future time steps to accept
+/// EN: Returns:
+/// EN: - Ok(i64): Time-step offset that matched (negative is past, positive is future)
+/// EN: - Err(TotpError): Code mismatch or errors during verification
+#[cfg(feature = "std")]
+pub fn verify_totp<S: SecretSource + ?Sized>(
+    secret: &S,
+    code: &str,
+    config: Option<TotpConfig>,
+    look_behind: u32,
+    look_ahead: u32,
+) -> Result<i64, TotpError> {
+    verify_totp_with_clock(secret, code, config, look_behind, look_ahead, &SystemClock)
+}
+
+/// CN: 使用指定时间源校验TOTP验证码，配置中的自定义时间戳优先于时间源
+/// EN: Verify a TOTP code using the given time source, a custom timestamp in the configuration taking precedence over it
+#[cfg(feature = "std")]
+pub fn verify_totp_with_clock<S: SecretSource + ?Sized, C: Clock + ?Sized>(
+    secret: &S,
+    code: &str,
+    config: Option<TotpConfig>,
+    look_behind: u32,
+    look_ahead: u32,
+    clock: &C,
+) -> Result<i64, TotpError> {
+    let config = config.unwrap_or_default();
+    config.validate()?;
+    let secret = secret.to_secret()?;
+    match_window(
+        code,
+        current_counter(&config, clock)?,
+        look_behind,
+        look_ahead,
+        None,
+        |step| compute_value(secret.as_bytes(), step, &config),
+    )
+}
+
+/// CN: 由原始密钥字节计算TOTP验证码数值，整个计算过程不进行堆分配，配置中的自定义时间戳优先于时间源
+/// CN: 参数:
+/// CN: - secret_bytes: 已解码的密钥字节
+/// CN: - config: TOTP配置
+/// CN: - clock: 时间源
+/// CN: 返回:
+/// CN: - Ok(OtpValue): 验证码数值及位数，可通过write_to写入调用方的缓冲区
+/// CN: - Err(TotpError): 配置无效或密钥过短
+///
+/// EN: Compute the TOTP code value from raw secret bytes without any heap allocation, a custom timestamp in the configuration taking precedence over the clock
+/// EN: Parameters:
+/// EN: - secret_bytes: Decoded secret bytes
+/// EN: - config: TOTP configuration
+/// EN: - clock: Time source
+/// EN: Returns:
+/// EN: - Ok(OtpValue): Code value and number of digits, writable into a caller buffer via write_to
+/// EN: - Err(TotpError): Invalid configuration or secret too short
+pub fn totp_value<C: Clock + ?Sized>(
+    secret_bytes: &[u8],
+    config: &TotpConfig,
+    clock: &C,
+) -> Result<OtpValue, TotpError> {
+    config.validate()?;
+    compute_value(secret_bytes, current_counter(config, clock)?, config)
+}
+
+/// CN: 由原始密钥字节计算TOTP验证码并写入缓冲区，返回写入部分的字符串视图，不进行堆分配
+/// EN: Compute the TOTP code from raw secret bytes into the buffer, returning a string view of the written part, without heap allocation
+pub fn write_totp_code<'a, C: Clock + ?Sized>(
+    secret_bytes: &[u8],
+    config: &TotpConfig,
+    clock: &C,
+    buf: &'a mut [u8],
+) -> Result<&'a str, TotpError> {
+    totp_value(secret_bytes, config, clock)?.write_to(buf)
+}
+
+/// CN: 使用任意摘要算法（实现Digest与BlockSizeUser，如厂商自定义哈希）计算TOTP验证码数值，不进行堆分配
+/// CN: 配置中的哈希算法被忽略，自定义时间戳优先于时间源
+///
+/// EN: Compute the TOTP code value with any digest (implementing Digest and BlockSizeUser, e.g. a custom vendor hash), without heap allocation
+/// EN: The configuration's hash algorithm is ignored and a custom timestamp takes precedence over the clock
+///
+/// ```
+/// use sha2::Sha256;
+/// use totp_sm_rs::utils::totp::clock::FixedClock;
+/// use totp_sm_rs::utils::totp::totp::{totp_value_with_digest, TotpConfig};
+///
+/// let config = TotpConfig {
+///     digits: 8,
+///     ..TotpConfig::default()
+/// };
+/// let secret = b"12345678901234567890123456789012";
+/// let value = totp_value_with_digest::<Sha256>(secret, &config, &FixedClock::from_unix(59));
+/// assert_eq!(value.unwrap().value(), 46119246);
+/// ```
+pub fn totp_value_with_digest<D: Digest + BlockSizeUser>(
+    secret_bytes: &[u8],
+    config: &TotpConfig,
+    clock: &(impl Clock + ?Sized),
+) -> Result<OtpValue, TotpError> {
+    config.validate()?;
+    check_key_length(secret_bytes, config)?;
+    let counter = current_counter(config, clock)?;
+    digest_value::<D>(secret_bytes, counter as u64, config.digits)
+}
+
+/// CN: 使用任意摘要算法生成TOTP验证码，配置中的哈希算法被忽略，自定义时间戳优先于时间源
+/// EN: Generate a TOTP code with any digest, ignoring the configuration's hash algorithm, a custom timestamp taking precedence over the clock
+#[cfg(feature = "std")]
+pub fn generate_totp_code_with_digest<D: Digest + BlockSizeUser>(
+    secret: &(impl SecretSource + ?Sized),
+    config: Option<TotpConfig>,
+    clock: &(impl Clock + ?Sized),
+) -> Result<String, TotpError> {
+    let config = config.unwrap_or_default();
+    config.validate()?;
+    let secret = secret.to_secret()?;
+    Ok(totp_value_with_digest::<D>(secret.as_bytes(), &config, clock)?.to_string())
+}
+
+/// CN: 使用任意摘要算法校验TOTP验证码，允许前后若干个时间步长的偏差，配置中的哈希算法被忽略
+/// CN: 返回匹配成功的时间步偏移量（负数表示过去，正数表示未来）
+///
+/// EN: Verify a TOTP code with any digest tolerating a skew of several time steps, ignoring the configuration's hash algorithm
+/// EN: Returns the matched time-step offset (negative is past, positive is future)
+#[cfg(feature = "std")]
+pub fn verify_totp_with_digest<D: Digest + BlockSizeUser>(
+    secret: &(impl SecretSource + ?Sized),
+    code: &str,
+    config: Option<TotpConfig>,
+    look_behind: u32,
+    look_ahead: u32,
+    clock: &(impl Clock + ?Sized),
+) -> Result<i64, TotpError> {
+    let config = config.unwrap_or_default();
+    config.validate()?;
+    let secret = secret.to_secret()?;
+    check_key_length(secret.as_bytes(), &config)?;
+    match_window(
+        code,
+        current_counter(&config, clock)?,
+        look_behind,
+        look_ahead,
+        None,
+        |step| digest_value::<D>(secret.as_bytes(), step as u64, config.digits),
+    )
+}
+
+/// CN: 在以给定计数为中心的窗口内查找与验证码匹配的时间步偏移量
+/// CN: 若提供了last_used_step，则不低于该值的时间步才会被接受，匹配到已使用时间步时返回ReplayedCode
+/// CN: value_at按时间步计算验证码数值
+///
+/// EN: Find the time-step offset matching the code within a window centred on the given counter
+/// EN: When last_used_step is given only later steps are accepted, and a match on a used step yields ReplayedCode
+/// EN: value_at computes the code value for a time step
+#[cfg(feature = "std")]
+pub(crate) fn match_window(
+    code: &str,
+    counter: i64,
+    look_behind: u32,
+    look_ahead: u32,
+    last_used_step: Option<i64>,
+    value_at: impl Fn(i64) -> Result<OtpValue, TotpError>,
+) -> Result<i64, TotpError> {
+    // CN: 按偏移量绝对值从小到大依次尝试，优先匹配最接近当前时间的步长
+    // EN: Try offsets in order of increasing distance, preferring the step closest to now
+    let mut replayed = false;
+    for (offset, step) in window_steps(counter, look_behind, look_ahead) {
+        if value_at(step)?.matches(code) {
+            if last_used_step.is_some_and(|last| step <= last) {
+                replayed = true;
+                continue;
+            }
+            return Ok(offset);
+        }
     }
-    // CN: Base32密钥解码和有效性验证
-    // EN: Decode Base32 secret and validate
-    let secret_bytes = match base32::decode(Rfc4648 { padding: false }, secret) {
-        Some(bytes) if !bytes.is_empty() => bytes,
-        _ => return Err(TotpError::Base32DecodeError),
-    };
+    if replayed {
+        Err(TotpError::ReplayedCode)
+    } else {
+        Err(TotpError::CodeMismatch)
+    }
+}
+
+/// CN: 校验窗口内的时间步偏移量，按绝对值从小到大排列（0, -1, 1, -2, 2, ...）
+/// EN: Time-step offsets within the verification window, ordered by increasing distance (0, -1, 1, -2, 2, ...)
+pub(crate) fn window_offsets(look_behind: u32, look_ahead: u32) -> impl Iterator<Item = i64> {
+    let max_distance = look_behind.max(look_ahead) as i64;
+    core::iter::once(0)
+        .chain((1..=max_distance).flat_map(|distance| [-distance, distance]))
+        .filter(move |&offset| offset >= -(look_behind as i64) && offset <= look_ahead as i64)
+}
+
+/// CN: 以counter为中心的校验窗口，按window_offsets的顺序产出（偏移量, 时间步），跳过早于T0的负数时间步
+/// EN: Verification window centred on counter, yielding (offset, time step) in window_offsets order and skipping negative steps before T0
+pub(crate) fn window_steps(
+    counter: i64,
+    look_behind: u32,
+    look_ahead: u32,
+) -> impl Iterator<Item = (i64, i64)> {
+    window_offsets(look_behind, look_ahead).filter_map(move |offset| {
+        counter
+            .checked_add(offset)
+            .filter(|step| *step >= 0)
+            .map(|step| (offset, step))
+    })
+}
+
+/// CN: 校验验证码位数（4至10位）
+/// EN: Validate the number of code digits (4 to 10)
+pub(crate) fn check_digits(digits: u8) -> Result<(), TotpError> {
+    if !(4..=10).contains(&digits) {
+        return Err(TotpError::InvalidDigits);
+    }
+    Ok(())
+}
+
+/// CN: 获取时间戳（自定义时间戳优先，否则读取时间源）并应用时区偏移，计算自T0起的当前时间步计数，早于T0时返回InvalidTimestamp
+/// EN: Get the timestamp (the custom one if set, otherwise from the clock), apply timezone offset and compute the current
+/// EN: time-step counter since T0, returning InvalidTimestamp before T0
+pub(crate) fn current_counter<C: Clock + ?Sized>(
+    config: &TotpConfig,
+    clock: &C,
+) -> Result<i64, TotpError> {
+    counter_at(config, current_time(config, clock)?)
+}
 
-    // CN: 获取时间戳并应用时区偏移
-    // EN: Get timestamp and apply timezone offset
-    let mut timestamp = match config.timestamp {
-        Some(ts) => ts,
-        None => Utc::now().timestamp(),
+/// CN: 获取应用时区偏移后的当前时间（自Unix纪元起的纳秒数），早于T0时返回InvalidTimestamp
+/// EN: Get the current time with the timezone offset applied (nanoseconds since the Unix epoch), returning InvalidTimestamp before T0
+pub(crate) fn current_time<C: Clock + ?Sized>(
+    config: &TotpConfig,
+    clock: &C,
+) -> Result<i128, TotpError> {
+    // CN: 当前时间保留纳秒部分，使亚秒级步长可用
+    // EN: The current time keeps its nanoseconds so that sub-second steps work
+    let (mut timestamp, nanos) = match config.timestamp {
+        Some(ts) => (ts, 0),
+        None => {
+            let now = clock.now();
+            (
+                i64::try_from(now.as_secs()).unwrap_or(i64::MAX),
+                now.subsec_nanos(),
+            )
+        }
     };
     if let Some(offset) = config.timezone_offset {
         timestamp = timestamp.saturating_add(offset);
     }
-    let time = timestamp / config.time_step as i64;
+    if timestamp < config.t0 {
+        return Err(TotpError::InvalidTimestamp);
+    }
+    Ok(timestamp as i128 * NANOS_PER_SECOND + nanos as i128)
+}
 
-    // CN: 将时间戳转换为大端字节数组
-    // EN: Convert timestamp to big-endian byte array
-    let time_bytes = time.to_be_bytes();
+/// CN: 计算给定时间（纳秒）所在的时间步计数
+/// EN: Compute the time-step counter containing the given time in nanoseconds
+pub(crate) fn counter_at(config: &TotpConfig, time: i128) -> Result<i64, TotpError> {
+    let elapsed = time - config.t0 as i128 * NANOS_PER_SECOND;
+    i64::try_from(elapsed / config.time_step.as_nanos() as i128)
+        .map_err(|_| TotpError::InvalidTimestamp)
+}
 
-    // CN: 根据选择的算法创建HMAC实例并计算
-    // EN: Create and calculate HMAC instance based on selected algorithm
-    let result = match config.hash_algorithm {
-        HashAlgorithm::SHA1 => {
-            let mut mac =
-                HmacSha1::new_from_slice(&secret_bytes).map_err(|_| TotpError::InvalidKeyLength)?;
-            mac.update(&time_bytes);
-            mac.finalize().into_bytes().to_vec()
-        }
-        HashAlgorithm::SHA256 => {
-            let mut mac = HmacSha256::new_from_slice(&secret_bytes)
-                .map_err(|_| TotpError::InvalidKeyLength)?;
-            mac.update(&time_bytes);
-            mac.finalize().into_bytes().to_vec()
-        }
-        HashAlgorithm::SHA512 => {
-            let mut mac = HmacSha512::new_from_slice(&secret_bytes)
-                .map_err(|_| TotpError::InvalidKeyLength)?;
-            mac.update(&time_bytes);
-            mac.finalize().into_bytes().to_vec()
-        }
-        HashAlgorithm::SM3 => {
-            let mut mac =
-                HmacSm3::new_from_slice(&secret_bytes).map_err(|_| TotpError::InvalidKeyLength)?;
-            mac.update(&time_bytes);
-            mac.finalize().into_bytes().to_vec()
-        }
-    };
+/// CN: 根据密钥和时间步计数计算验证码
+/// EN: Compute the code for the given secret bytes and time-step counter
+#[cfg(feature = "std")]
+pub(crate) fn compute_code(
+    secret_bytes: &[u8],
+    counter: i64,
+    config: &TotpConfig,
+) -> Result<String, TotpError> {
+    Ok(compute_value(secret_bytes, counter, config)?.to_string())
+}
 
-    // CN: 动态截取偏移量（根据RFC 6238标准）
-    // EN: Dynamic truncation offset (according to RFC 6238)
-    let offset = (result[result.len() - 1] & 0xf) as usize;
+/// CN: 根据密钥和时间步计数计算验证码数值，不进行堆分配
+/// EN: Compute the code value for the given secret bytes and time-step counter, without heap allocation
+pub(crate) fn compute_value(
+    secret_bytes: &[u8],
+    counter: i64,
+    config: &TotpConfig,
+) -> Result<OtpValue, TotpError> {
+    check_key_length(secret_bytes, config)?;
+
+    // CN: 时间步计数按其二进制补码作为HOTP计数器
+    // EN: The time-step counter is used as the HOTP counter via its two's complement bits
+    compute_hotp_value(
+        secret_bytes,
+        counter as u64,
+        config.digits,
+        config.hash_algorithm,
+    )
+}
 
-    // CN: 验证密钥长度（至少16字节，符合安全要求）
-    // EN: Validate key length (minimum 16 bytes for security)
+/// CN: 启用安全检查时验证密钥长度（至少16字节，符合安全要求）
+/// EN: Validate key length when the security check is enabled (minimum 16 bytes for security)
+fn check_key_length(secret_bytes: &[u8], config: &TotpConfig) -> Result<(), TotpError> {
     if config.is_check_security && secret_bytes.len() < 16 {
         return Err(TotpError::InvalidKeyLength);
     }
-
-    // CN: 根据RFC 6238标准计算最终的验证码
-    // EN: Calculate final code according to RFC 6238
-    let code = ((result[offset] as u64 & 0x7f) << 24
-        | (result[offset + 1] as u64 & 0xff) << 16
-        | (result[offset + 2] as u64 & 0xff) << 8
-        | (result[offset + 3] as u64 & 0xff))
-        % 10u64.pow(config.digits as u32);
-
-    Ok(format!(
-        "{:0digits$}",
-        code,
-        digits = config.digits as usize
-    ))
+    Ok(())
 }
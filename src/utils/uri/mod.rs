@@ -0,0 +1,3 @@
+#[allow(clippy::module_inception)]
+pub mod uri;
+pub use uri::{OtpAuthUri, OtpType, ParseMode, UriError};
@@ -0,0 +1,440 @@
+use crate::utils::i18n::message;
+use crate::utils::secret::secret::{Secret, SecretSource};
+use crate::utils::totp::totp::{check_digits, HashAlgorithm, TotpConfig, TotpError};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+/// CN: otpauth URI 的协议前缀
+/// EN: Scheme prefix of otpauth URIs
+const SCHEME: &str = "otpauth://";
+
+/// CN: 标签与参数值中需要百分号编码的字符（保留RFC 3986非保留字符）
+/// EN: Characters percent-encoded in labels and parameter values (RFC 3986 unreserved characters are kept)
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// CN: otpauth URI 错误类型枚举
+/// EN: otpauth URI error type enumeration
+#[derive(Debug, PartialEq, Eq)]
+pub enum UriError {
+    /// CN: 协议不是 otpauth://
+    /// EN: Scheme is not otpauth://
+    InvalidScheme,
+    /// CN: 不支持的口令类型，附带原始值
+    /// EN: Unsupported OTP type, with the original value
+    InvalidType(String),
+    /// CN: 标签为空或无法解码
+    /// EN: Label is empty or cannot be decoded
+    InvalidLabel,
+    /// CN: 缺少必需的参数，附带参数名
+    /// EN: A required parameter is missing, with the parameter name
+    MissingParameter(&'static str),
+    /// CN: 参数值无效，附带参数名
+    /// EN: Parameter value is invalid, with the parameter name
+    InvalidParameter(&'static str),
+    /// CN: 严格模式下出现未知参数，附带参数名
+    /// EN: Unknown parameter in strict mode, with the parameter name
+    UnknownParameter(String),
+    /// CN: 严格模式下参数重复出现，附带参数名
+    /// EN: Duplicated parameter in strict mode, with the parameter name
+    DuplicateParameter(String),
+    /// CN: 严格模式下标签中的发行方与issuer参数不一致
+    /// EN: Issuer in the label differs from the issuer parameter in strict mode
+    IssuerMismatch,
+}
+
+/// CN: 为UriError实现显示特征，提供错误信息的国际化支持
+/// EN: Implement Display trait for UriError with internationalization support
+impl fmt::Display for UriError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UriError::InvalidScheme => write!(f, "{}", message("uri.errors.invalid_scheme")),
+            UriError::InvalidType(value) => {
+                write!(f, "{}: {}", message("uri.errors.invalid_type"), value)
+            }
+            UriError::InvalidLabel => write!(f, "{}", message("uri.errors.invalid_label")),
+            UriError::MissingParameter(name) => {
+                write!(f, "{}: {}", message("uri.errors.missing_parameter"), name)
+            }
+            UriError::InvalidParameter(name) => {
+                write!(f, "{}: {}", message("uri.errors.invalid_parameter"), name)
+            }
+            UriError::UnknownParameter(name) => {
+                write!(f, "{}: {}", message("uri.errors.unknown_parameter"), name)
+            }
+            UriError::DuplicateParameter(name) => {
+                write!(f, "{}: {}", message("uri.errors.duplicate_parameter"), name)
+            }
+            UriError::IssuerMismatch => write!(f, "{}", message("uri.errors.issuer_mismatch")),
+        }
+    }
+}
+
+/// CN: URI 解析模式
+/// EN: URI parsing mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// CN: 严格模式：拒绝未知或重复的参数、不一致的发行方、非规范的密钥与算法写法，HOTP必须提供counter
+    /// EN: Strict mode: rejects unknown or duplicated parameters, conflicting issuers, non-canonical
+    /// EN: secrets and algorithm names, and requires counter for HOTP
+    #[default]
+    Strict,
+    /// CN: 宽松模式：忽略未知参数，重复参数取第一个，以issuer参数为准，
+    /// CN: 容忍密钥中的空格、填充与小写字母以及算法名的大小写，HOTP的counter缺省为0
+    ///
+    /// EN: Lenient mode: ignores unknown parameters, keeps the first of duplicated ones, prefers the issuer
+    /// EN: parameter, tolerates spaces, padding and lowercase letters in the secret as well as the case of
+    /// EN: the algorithm name, and defaults the HOTP counter to 0
+    Lenient,
+}
+
+/// CN: 口令类型及其类型相关参数
+/// EN: OTP type with its type-specific parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtpType {
+    /// CN: 基于时间的口令，附带时间步长（秒）
+    /// EN: Time-based OTP, with the time step in seconds
+    Totp { period: u32 },
+    /// CN: 基于计数器的口令，附带计数器初值
+    /// EN: Counter-based OTP, with the initial counter
+    Hotp { counter: u64 },
+}
+
+/// CN: Key URI Format（otpauth://）表示的口令凭据，
+/// CN: 如 `otpauth://totp/Issuer:alice?secret=...&algorithm=SHA256&digits=8&period=60`
+/// CN: algorithm参数除SHA1/SHA256/SHA512外还支持 `SM3` 扩展值
+///
+/// EN: OTP credential in the Key URI Format (otpauth://),
+/// EN: e.g. `otpauth://totp/Issuer:alice?secret=...&algorithm=SHA256&digits=8&period=60`
+/// EN: Besides SHA1/SHA256/SHA512, the algorithm parameter accepts the `SM3` extension value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtpAuthUri {
+    /// CN: 口令类型（TOTP或HOTP）
+    /// EN: OTP type (TOTP or HOTP)
+    pub otp_type: OtpType,
+    /// CN: 发行方
+    /// EN: Issuer
+    pub issuer: Option<String>,
+    /// CN: 账户名
+    /// EN: Account name
+    pub account_name: String,
+    /// CN: 密钥，调试输出时隐藏，生成URI时按大写无填充的Base32编码
+    /// EN: Secret key, redacted in Debug and encoded as uppercase unpadded Base32 in the URI
+    pub secret: Secret,
+    /// CN: 使用的哈希算法
+    /// EN: Hash algorithm to use
+    pub algorithm: HashAlgorithm,
+    /// CN: 验证码位数（4至10位）
+    /// EN: Number of code digits (4 to 10)
+    pub digits: u8,
+}
+
+impl OtpAuthUri {
+    /// CN: 根据TOTP配置创建URI，时间步长按整秒向下取整，URI无法表示的T0被忽略
+    /// CN: 位数不在4至10位时返回InvalidDigits，时间步长不足1秒或超出u32秒时返回InvalidTimeStep
+    ///
+    /// EN: Create a URI from a TOTP configuration, truncating the time step to whole seconds and ignoring T0, which URIs cannot express
+    /// EN: Returns InvalidDigits for digits outside 4 to 10, and InvalidTimeStep for a time step under 1 second or beyond u32 seconds
+    pub fn new_totp<S: SecretSource + ?Sized>(
+        issuer: Option<&str>,
+        account_name: &str,
+        secret: &S,
+        config: &TotpConfig,
+    ) -> Result<Self, TotpError> {
+        check_digits(config.digits)?;
+        let period = u32::try_from(config.time_step.as_secs())
+            .ok()
+            .filter(|period| *period > 0)
+            .ok_or(TotpError::InvalidTimeStep)?;
+        Ok(Self {
+            otp_type: OtpType::Totp { period },
+            issuer: issuer.map(str::to_string),
+            account_name: account_name.to_string(),
+            secret: secret.to_secret()?,
+            algorithm: config.hash_algorithm,
+            digits: config.digits,
+        })
+    }
+
+    /// CN: 根据HOTP参数创建URI，位数不在4至10位时返回InvalidDigits
+    /// EN: Create a URI from HOTP parameters, returning InvalidDigits for digits outside 4 to 10
+    pub fn new_hotp<S: SecretSource + ?Sized>(
+        issuer: Option<&str>,
+        account_name: &str,
+        secret: &S,
+        counter: u64,
+        digits: u8,
+        algorithm: HashAlgorithm,
+    ) -> Result<Self, TotpError> {
+        check_digits(digits)?;
+        Ok(Self {
+            otp_type: OtpType::Hotp { counter },
+            issuer: issuer.map(str::to_string),
+            account_name: account_name.to_string(),
+            secret: secret.to_secret()?,
+            algorithm,
+            digits,
+        })
+    }
+
+    /// CN: 转换为TOTP配置，HOTP类型的URI返回None
+    /// EN: Convert to a TOTP configuration, returning None for HOTP URIs
+    pub fn totp_config(&self) -> Option<TotpConfig> {
+        match self.otp_type {
+            OtpType::Totp { period } => Some(TotpConfig {
+                digits: self.digits,
+                time_step: Duration::from_secs(period as u64),
+                hash_algorithm: self.algorithm,
+                ..TotpConfig::default()
+            }),
+            OtpType::Hotp { .. } => None,
+        }
+    }
+
+    /// CN: 按指定模式解析otpauth URI
+    /// CN: 参数:
+    /// CN: - uri: 待解析的URI字符串
+    /// CN: - mode: 解析模式
+    /// CN: 返回:
+    /// CN: - Ok(OtpAuthUri): 解析得到的凭据
+    /// CN: - Err(UriError): URI格式或参数错误
+    ///
+    /// EN: Parse an otpauth URI with the given mode
+    /// EN: Parameters:
+    /// EN: - uri: URI string to parse
+    /// EN: - mode: Parsing mode
+    /// EN: Returns:
+    /// EN: - Ok(OtpAuthUri): Parsed credential
+    /// EN: - Err(UriError): Malformed URI or parameter
+    pub fn parse(uri: &str, mode: ParseMode) -> Result<Self, UriError> {
+        let strict = mode == ParseMode::Strict;
+        let rest = match uri.get(..SCHEME.len()) {
+            Some(scheme) if scheme == SCHEME => &uri[SCHEME.len()..],
+            Some(scheme) if !strict && scheme.eq_ignore_ascii_case(SCHEME) => &uri[SCHEME.len()..],
+            _ => return Err(UriError::InvalidScheme),
+        };
+
+        let (otp_type, rest) = rest.split_once('/').ok_or(UriError::InvalidLabel)?;
+        let is_totp = match otp_type {
+            "totp" => true,
+            "hotp" => false,
+            _ if !strict && otp_type.eq_ignore_ascii_case("totp") => true,
+            _ if !strict && otp_type.eq_ignore_ascii_case("hotp") => false,
+            _ => return Err(UriError::InvalidType(otp_type.to_string())),
+        };
+        let (label, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (label_issuer, account_name) = parse_label(label, strict)?;
+
+        // CN: 逐个收集已知参数，严格模式下拒绝未知与重复参数
+        // EN: Collect known parameters one by one, rejecting unknown and duplicated ones in strict mode
+        let mut params = Params::default();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let (name, slot) = match key {
+                "secret" => ("secret", &mut params.secret),
+                "issuer" => ("issuer", &mut params.issuer),
+                "algorithm" => ("algorithm", &mut params.algorithm),
+                "digits" => ("digits", &mut params.digits),
+                "period" if is_totp => ("period", &mut params.period),
+                "counter" if !is_totp => ("counter", &mut params.counter),
+                _ if strict => return Err(UriError::UnknownParameter(key.to_string())),
+                _ => continue,
+            };
+            if slot.is_some() {
+                if strict {
+                    return Err(UriError::DuplicateParameter(name.to_string()));
+                }
+                continue;
+            }
+            *slot = Some(decode_component(value, strict).ok_or(UriError::InvalidParameter(name))?);
+        }
+
+        let secret = params.secret.ok_or(UriError::MissingParameter("secret"))?;
+        let secret = normalize_secret(&secret, strict)?;
+
+        let issuer = match (label_issuer, params.issuer) {
+            (Some(label), Some(param)) if strict && label != param => {
+                return Err(UriError::IssuerMismatch)
+            }
+            (label, param) => param.or(label),
+        };
+
+        let algorithm = match params.algorithm {
+            Some(name) => parse_algorithm(&name, strict)?,
+            None => HashAlgorithm::default(),
+        };
+
+        let digits = match params.digits {
+            Some(digits) => digits
+                .parse()
+                .ok()
+                .filter(|digits| check_digits(*digits).is_ok())
+                .ok_or(UriError::InvalidParameter("digits"))?,
+            None => 6,
+        };
+
+        let otp_type = if is_totp {
+            let period = match params.period {
+                Some(period) => period
+                    .parse()
+                    .ok()
+                    .filter(|period| *period > 0)
+                    .ok_or(UriError::InvalidParameter("period"))?,
+                None => 30,
+            };
+            OtpType::Totp { period }
+        } else {
+            let counter = match params.counter {
+                Some(counter) => counter
+                    .parse()
+                    .map_err(|_| UriError::InvalidParameter("counter"))?,
+                None if strict => return Err(UriError::MissingParameter("counter")),
+                None => 0,
+            };
+            OtpType::Hotp { counter }
+        };
+
+        Ok(Self {
+            otp_type,
+            issuer,
+            account_name,
+            secret,
+            algorithm,
+            digits,
+        })
+    }
+}
+
+/// CN: 以严格模式解析otpauth URI
+/// EN: Parse an otpauth URI in strict mode
+impl FromStr for OtpAuthUri {
+    type Err = UriError;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        Self::parse(uri, ParseMode::Strict)
+    }
+}
+
+/// CN: 生成规范的otpauth URI，显式写出算法、位数及周期或计数器
+/// EN: Generate a canonical otpauth URI, always spelling out algorithm, digits and period or counter
+impl fmt::Display for OtpAuthUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let otp_type = match self.otp_type {
+            OtpType::Totp { .. } => "totp",
+            OtpType::Hotp { .. } => "hotp",
+        };
+        write!(f, "{}{}/", SCHEME, otp_type)?;
+        if let Some(issuer) = &self.issuer {
+            write!(f, "{}:", utf8_percent_encode(issuer, COMPONENT))?;
+        }
+        write!(
+            f,
+            "{}?secret={}",
+            utf8_percent_encode(&self.account_name, COMPONENT),
+            Zeroizing::new(self.secret.to_base32()).as_str()
+        )?;
+        if let Some(issuer) = &self.issuer {
+            write!(f, "&issuer={}", utf8_percent_encode(issuer, COMPONENT))?;
+        }
+        write!(f, "&algorithm={}&digits={}", self.algorithm, self.digits)?;
+        match self.otp_type {
+            OtpType::Totp { period } => write!(f, "&period={}", period),
+            OtpType::Hotp { counter } => write!(f, "&counter={}", counter),
+        }
+    }
+}
+
+/// CN: 已知参数的原始值
+/// EN: Raw values of the known parameters
+#[derive(Default)]
+struct Params {
+    secret: Option<String>,
+    issuer: Option<String>,
+    algorithm: Option<String>,
+    digits: Option<String>,
+    period: Option<String>,
+    counter: Option<String>,
+}
+
+/// CN: 解码百分号编码，宽松模式下额外将 `+` 视为空格
+/// EN: Decode percent-encoding, additionally treating `+` as a space in lenient mode
+fn decode_component(value: &str, strict: bool) -> Option<String> {
+    let value = if strict {
+        value.to_string()
+    } else {
+        value.replace('+', " ")
+    };
+    percent_decode_str(&value)
+        .decode_utf8()
+        .ok()
+        .map(|decoded| decoded.into_owned())
+}
+
+/// CN: 解析 `发行方:账户名` 形式的标签，分隔符可以是 `:` 或 `%3A`
+/// EN: Parse a label of the form `issuer:account`, where the separator may be `:` or `%3A`
+fn parse_label(label: &str, strict: bool) -> Result<(Option<String>, String), UriError> {
+    let (issuer, account_name) = match label.split_once(':') {
+        Some((issuer, account_name)) => (
+            Some(decode_component(issuer, strict).ok_or(UriError::InvalidLabel)?),
+            decode_component(account_name, strict).ok_or(UriError::InvalidLabel)?,
+        ),
+        None => {
+            let decoded = decode_component(label, strict).ok_or(UriError::InvalidLabel)?;
+            match decoded.split_once(':') {
+                Some((issuer, account_name)) => {
+                    (Some(issuer.to_string()), account_name.to_string())
+                }
+                None => (None, decoded),
+            }
+        }
+    };
+    // CN: 规范允许分隔符后出现空格
+    // EN: The format allows spaces after the separator
+    let account_name = account_name.trim_start().to_string();
+    if account_name.is_empty() || issuer.as_deref().is_some_and(str::is_empty) {
+        return Err(UriError::InvalidLabel);
+    }
+    Ok((issuer, account_name))
+}
+
+/// CN: 校验并规范化Base32密钥，宽松模式下去除空格、连字符与填充并转为大写
+/// EN: Validate and normalise the Base32 secret, stripping spaces, hyphens and padding and uppercasing in lenient mode
+fn normalize_secret(secret: &str, strict: bool) -> Result<Secret, UriError> {
+    let secret = if strict {
+        secret.to_string()
+    } else {
+        secret
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '='))
+            .collect::<String>()
+            .to_ascii_uppercase()
+    };
+    let secret = Zeroizing::new(secret);
+    let canonical = secret
+        .chars()
+        .all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c));
+    if !canonical {
+        return Err(UriError::InvalidParameter("secret"));
+    }
+    Secret::from_base32(&secret).map_err(|_| UriError::InvalidParameter("secret"))
+}
+
+/// CN: 解析algorithm参数，严格模式要求与算法名称完全一致，宽松模式接受HashAlgorithm的全部解析写法
+/// EN: Parse the algorithm parameter, requiring the exact algorithm name in strict mode and accepting every HashAlgorithm spelling in lenient mode
+fn parse_algorithm(name: &str, strict: bool) -> Result<HashAlgorithm, UriError> {
+    let algorithm = if strict {
+        HashAlgorithm::ALL
+            .iter()
+            .copied()
+            .find(|algorithm| algorithm.name() == name)
+    } else {
+        name.parse().ok()
+    };
+    algorithm.ok_or(UriError::InvalidParameter("algorithm"))
+}
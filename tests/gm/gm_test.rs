@@ -0,0 +1,244 @@
+use totp_sm_rs::utils::gm::gm::{
+    generate_gm_code, generate_gm_code_with_clock, verify_gm_code, verify_gm_code_with_clock,
+    GmAlgorithm, GmConfig, GM_DIGITS,
+};
+use totp_sm_rs::utils::totp::clock::FixedClock;
+use totp_sm_rs::utils::totp::totp::TotpError;
+
+/// 种子密钥 1234567890abcdef1234567890abcdef 的Base32编码
+const SECRET: &str = "CI2FM6EQVPG66ERUKZ4JBK6N54";
+const TIMESTAMP: i64 = 1313280000;
+
+fn config_at(timestamp: i64) -> GmConfig {
+    GmConfig {
+        timestamp: Some(timestamp),
+        ..GmConfig::default()
+    }
+}
+
+/// 回归向量：(事件因子, 挑战因子, 期望口令)
+/// 手头没有 GM/T 0021 附录的测试数据，期望值由本实现计算得出，仅用于防止回归，不能证明与标准一致
+const SM3_TEST_VECTORS: [(Option<u32>, Option<&str>, &str); 3] = [
+    (None, None, "104876"),
+    (Some(1234), None, "639647"),
+    (Some(1234), Some("5678"), "811533"),
+];
+
+/// 测试SM3动态口令的时间、事件、挑战因子组合
+#[test]
+fn test_sm3_test_vectors() {
+    for (counter, challenge, expected) in SM3_TEST_VECTORS {
+        let config = GmConfig {
+            counter,
+            ..config_at(TIMESTAMP)
+        };
+        let code = generate_gm_code(SECRET, challenge, Some(config)).unwrap();
+        assert_eq!(
+            code, expected,
+            "counter {:?} challenge {:?}",
+            counter, challenge
+        );
+        assert_eq!(code.len(), GM_DIGITS);
+    }
+}
+
+/// 测试不使用时间因子的事件型与挑战型口令
+#[test]
+fn test_sm3_without_time_factor() {
+    let event = GmConfig {
+        time_step: None,
+        counter: Some(1234),
+        ..GmConfig::default()
+    };
+    assert_eq!(
+        generate_gm_code(SECRET, None, Some(event)).unwrap(),
+        "411602"
+    );
+
+    let challenge = GmConfig {
+        time_step: None,
+        ..GmConfig::default()
+    };
+    assert_eq!(
+        generate_gm_code(SECRET, Some("56781234"), Some(challenge)).unwrap(),
+        "612035"
+    );
+
+    // 未提供任何因子
+    assert!(matches!(
+        generate_gm_code(SECRET, None, Some(challenge)),
+        Err(TotpError::InvalidFactor)
+    ));
+}
+
+/// 测试自定义起始时间与周期
+#[test]
+fn test_sm3_epoch_and_time_step() {
+    let config = GmConfig {
+        time_step: Some(30),
+        epoch: 1313200000,
+        ..config_at(TIMESTAMP)
+    };
+    assert_eq!(
+        generate_gm_code(SECRET, None, Some(config)).unwrap(),
+        "552045"
+    );
+
+    // 早于起始时间或周期超出1至60秒的配置无效
+    let before_epoch = GmConfig {
+        timestamp: Some(1313100000),
+        ..config
+    };
+    assert!(matches!(
+        generate_gm_code(SECRET, None, Some(before_epoch)),
+        Err(TotpError::InvalidFactor)
+    ));
+    let long_step = GmConfig {
+        time_step: Some(61),
+        ..config
+    };
+    assert!(matches!(
+        generate_gm_code(SECRET, None, Some(long_step)),
+        Err(TotpError::InvalidFactor)
+    ));
+}
+
+/// 测试校验窗口
+#[test]
+fn test_verify_window() {
+    let config = config_at(TIMESTAMP);
+    assert_eq!(
+        verify_gm_code(SECRET, "104876", None, Some(config), 1, 1).unwrap(),
+        0
+    );
+    assert_eq!(
+        verify_gm_code(SECRET, "936900", None, Some(config), 1, 1).unwrap(),
+        -1
+    );
+    assert_eq!(
+        verify_gm_code(SECRET, "951507", None, Some(config), 1, 1).unwrap(),
+        1
+    );
+    assert!(matches!(
+        verify_gm_code(SECRET, "951507", None, Some(config), 1, 0),
+        Err(TotpError::CodeMismatch)
+    ));
+}
+
+/// 测试密钥与挑战因子长度要求
+#[test]
+fn test_invalid_inputs() {
+    // 密钥不足128比特
+    assert!(matches!(
+        generate_gm_code("GEZDGNBVGY3TQOJQ", None, Some(config_at(TIMESTAMP))),
+        Err(TotpError::InvalidKeyLength)
+    ));
+    // 挑战因子不足4字节
+    assert!(matches!(
+        generate_gm_code(SECRET, Some("567"), Some(config_at(TIMESTAMP))),
+        Err(TotpError::InvalidFactor)
+    ));
+}
+
+/// 回归向量：(事件因子, 挑战因子, 期望口令)
+/// 与SM3相同，期望值由本实现计算得出而非 GM/T 0021 附录数据，仅用于防止回归
+const SM4_TEST_VECTORS: [(Option<u32>, Option<&str>, &str); 3] = [
+    (None, None, "373706"),
+    (Some(1234), Some("5678"), "809874"),
+    (Some(1234), Some("abcdefghijklmnop"), "162543"),
+];
+
+fn sm4_config_at(timestamp: i64) -> GmConfig {
+    GmConfig {
+        algorithm: GmAlgorithm::SM4,
+        ..config_at(timestamp)
+    }
+}
+
+/// 测试SM4动态口令，包括ID超过一个分组时的CBC链接
+#[test]
+fn test_sm4_test_vectors() {
+    for (counter, challenge, expected) in SM4_TEST_VECTORS {
+        let config = GmConfig {
+            counter,
+            ..sm4_config_at(TIMESTAMP)
+        };
+        let code = generate_gm_code(SECRET, challenge, Some(config)).unwrap();
+        assert_eq!(
+            code, expected,
+            "counter {:?} challenge {:?}",
+            counter, challenge
+        );
+    }
+}
+
+/// 测试SM4不使用时间因子的口令，以及非128比特种子密钥的SM3压缩
+#[test]
+fn test_sm4_factors_and_key_derivation() {
+    let event = GmConfig {
+        time_step: None,
+        counter: Some(1234),
+        ..sm4_config_at(TIMESTAMP)
+    };
+    assert_eq!(
+        generate_gm_code(SECRET, None, Some(event)).unwrap(),
+        "248099"
+    );
+
+    let challenge = GmConfig {
+        time_step: None,
+        ..sm4_config_at(TIMESTAMP)
+    };
+    assert_eq!(
+        generate_gm_code(SECRET, Some("56781234"), Some(challenge)).unwrap(),
+        "720807"
+    );
+
+    // 种子密钥 1234567890abcdef1234567890abcdef0011223344（168比特）
+    let long_secret = "CI2FM6EQVPG66ERUKZ4JBK6N54ABCIRTIQ";
+    assert_eq!(
+        generate_gm_code(long_secret, None, Some(sm4_config_at(TIMESTAMP))).unwrap(),
+        "349607"
+    );
+}
+
+/// 测试SM4口令的校验，以及SM3与SM4口令互不通用
+#[test]
+fn test_sm4_verify() {
+    let config = sm4_config_at(TIMESTAMP);
+    assert_eq!(
+        verify_gm_code(SECRET, "373706", None, Some(config), 1, 1).unwrap(),
+        0
+    );
+    assert!(matches!(
+        verify_gm_code(SECRET, "104876", None, Some(config), 1, 1),
+        Err(TotpError::CodeMismatch)
+    ));
+}
+
+/// 测试使用指定时间源生成与校验口令，以及时间因子在起始时间附近不回溯到负值
+#[test]
+fn test_gm_with_clock() {
+    let clock = FixedClock::from_unix(TIMESTAMP as u64);
+    assert_eq!(
+        generate_gm_code_with_clock(SECRET, None, None, &clock).unwrap(),
+        "104876"
+    );
+    let later = FixedClock::from_unix(TIMESTAMP as u64 + 60);
+    assert_eq!(
+        verify_gm_code_with_clock(SECRET, "104876", None, None, 1, 0, &later).unwrap(),
+        -1
+    );
+    // 自定义时间戳优先于时间源
+    assert_eq!(
+        generate_gm_code_with_clock(SECRET, None, Some(config_at(TIMESTAMP)), &later).unwrap(),
+        "104876"
+    );
+
+    let epoch = FixedClock::from_unix(0);
+    let code = generate_gm_code_with_clock(SECRET, None, None, &epoch).unwrap();
+    assert_eq!(
+        verify_gm_code_with_clock(SECRET, &code, None, None, 5, 5, &epoch).unwrap(),
+        0
+    );
+}
@@ -0,0 +1 @@
+pub mod gm_test;
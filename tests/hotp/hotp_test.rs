@@ -0,0 +1,71 @@
+use totp_sm_rs::utils::hotp::hotp::{generate_hotp_code, verify_hotp};
+use totp_sm_rs::utils::totp::totp::{HashAlgorithm, TotpError};
+
+/// RFC 4226 附录D 测试密钥 "12345678901234567890" 的Base32编码
+const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+/// RFC 4226 附录D 测试向量（计数器0至9）
+const RFC_TEST_VECTORS: [&str; 10] = [
+    "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583", "399871",
+    "520489",
+];
+
+/// 测试生成HOTP代码是否符合RFC 4226标准
+#[test]
+fn test_rfc_test_vectors() {
+    for (counter, expected) in RFC_TEST_VECTORS.iter().enumerate() {
+        let code = generate_hotp_code(SECRET, counter as u64, 6, HashAlgorithm::SHA1).unwrap();
+        assert_eq!(
+            &code, expected,
+            "HOTP validation failed for counter {}",
+            counter
+        );
+    }
+}
+
+/// 测试校验HOTP并返回新的计数器
+#[test]
+fn test_verify_returns_next_counter() {
+    let next = verify_hotp(SECRET, "755224", 0, 6, HashAlgorithm::SHA1, 0).unwrap();
+    assert_eq!(next, 1);
+
+    // 预读窗口内的验证码被接受，计数器跳到匹配位置之后
+    let next = verify_hotp(SECRET, "162583", 1, 6, HashAlgorithm::SHA1, 10).unwrap();
+    assert_eq!(next, 8);
+}
+
+/// 测试预读窗口之外及已使用的验证码被拒绝
+#[test]
+fn test_verify_outside_window() {
+    let result = verify_hotp(SECRET, "162583", 1, 6, HashAlgorithm::SHA1, 5);
+    assert!(matches!(result, Err(TotpError::CodeMismatch)));
+
+    // 计数器之前的验证码不再被接受
+    let result = verify_hotp(SECRET, "755224", 1, 6, HashAlgorithm::SHA1, 10);
+    assert!(matches!(result, Err(TotpError::CodeMismatch)));
+}
+
+/// 测试计数器为u64::MAX时匹配成功也不会回绕到0
+#[test]
+fn test_verify_counter_exhausted() {
+    let code = generate_hotp_code(SECRET, u64::MAX, 6, HashAlgorithm::SHA1).unwrap();
+    let result = verify_hotp(SECRET, &code, u64::MAX, 6, HashAlgorithm::SHA1, 0);
+    assert!(matches!(result, Err(TotpError::CounterExhausted)));
+    let result = verify_hotp(SECRET, &code, u64::MAX - 1, 6, HashAlgorithm::SHA1, 3);
+    assert!(matches!(result, Err(TotpError::CounterExhausted)));
+
+    let code = generate_hotp_code(SECRET, u64::MAX - 1, 6, HashAlgorithm::SHA1).unwrap();
+    let next = verify_hotp(SECRET, &code, u64::MAX - 1, 6, HashAlgorithm::SHA1, 0).unwrap();
+    assert_eq!(next, u64::MAX);
+}
+
+/// 测试无效参数
+#[test]
+fn test_invalid_parameters() {
+    assert!(matches!(
+        generate_hotp_code("12345", 0, 6, HashAlgorithm::SHA1),
+        Err(TotpError::Base32DecodeError)
+    ));
+    assert!(generate_hotp_code(SECRET, 0, 3, HashAlgorithm::SHA1).is_err());
+    assert!(generate_hotp_code(SECRET, 0, 11, HashAlgorithm::SHA1).is_err());
+}
@@ -0,0 +1 @@
+pub mod hotp_test;
@@ -0,0 +1,217 @@
+use std::time::Duration;
+use totp_sm_rs::utils::migration::migration::{
+    decode_migration_batch, decode_migration_uri, decode_migration_uris, encode_migration_uris,
+    MigrationError,
+};
+use totp_sm_rs::utils::totp::totp::{HashAlgorithm, TotpConfig};
+use totp_sm_rs::utils::uri::uri::{OtpAuthUri, OtpType};
+
+/// Google Authenticator 导出的单账户迁移URI（密钥 JBSWY3DPEHPK3PXP，Example:alice@google.com）
+const GOOGLE_EXPORT: &str = "otpauth-migration://offline?data=CjEKCkhlbGxvId6tvu8SGEV4YW1wbGU6YWxpY2VAZ29vZ2xlLmNvbRoHRXhhbXBsZSABKAEwAhABGAEgACjr4JKk%2Bv%2F%2F%2F%2F8B";
+
+/// 测试解析 Google Authenticator 导出的TOTP账户
+#[test]
+fn test_decode_google_export() {
+    let accounts = decode_migration_uri(GOOGLE_EXPORT).unwrap();
+    assert_eq!(accounts.len(), 1);
+    let account = &accounts[0];
+    assert_eq!(account.otp_type, OtpType::Totp { period: 30 });
+    assert_eq!(account.issuer.as_deref(), Some("Example"));
+    assert_eq!(account.account_name, "alice@google.com");
+    assert_eq!(account.secret.to_base32(), "JBSWY3DPEHPK3PXP");
+    assert_eq!(account.algorithm, HashAlgorithm::SHA1);
+    assert_eq!(account.digits, 6);
+}
+
+/// 测试HOTP账户及SHA256、8位参数（按迁移Protobuf格式手工构造）
+#[test]
+fn test_decode_hotp_account() {
+    let accounts = decode_migration_uri(
+        "otpauth-migration://offline?data=CikKFDEyMzQ1Njc4OTAxMjM0NTY3ODkwEgNib2IaBEFDTUUgAigCMAE4KhAB",
+    )
+    .unwrap();
+    let account = &accounts[0];
+    assert_eq!(account.otp_type, OtpType::Hotp { counter: 42 });
+    assert_eq!(account.issuer.as_deref(), Some("ACME"));
+    assert_eq!(account.account_name, "bob");
+    assert_eq!(
+        account.secret.to_base32(),
+        "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"
+    );
+    assert_eq!(account.algorithm, HashAlgorithm::SHA256);
+    assert_eq!(account.digits, 8);
+}
+
+/// 测试未指定的枚举值按默认参数处理
+#[test]
+fn test_decode_unspecified_enums() {
+    let accounts =
+        decode_migration_uri("otpauth-migration://offline?data=ChEKBkhlbGxvIRIBeCAAKAAwAA%3D%3D")
+            .unwrap();
+    assert_eq!(accounts.len(), 1);
+    let account = &accounts[0];
+    assert_eq!(account.otp_type, OtpType::Totp { period: 30 });
+    assert_eq!(account.digits, 6);
+    assert_eq!(account.algorithm, HashAlgorithm::SHA1);
+}
+
+/// 测试无效的迁移URI与数据
+#[test]
+fn test_invalid_migration() {
+    let cases = [
+        (
+            "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP",
+            MigrationError::InvalidUri,
+        ),
+        (
+            "otpauth-migration://offline?version=1",
+            MigrationError::InvalidUri,
+        ),
+        (
+            "otpauth-migration://offline?data=%%%",
+            MigrationError::InvalidData,
+        ),
+        (
+            "otpauth-migration://offline?data=CjEKCkhl",
+            MigrationError::InvalidData,
+        ),
+        // 使用MD5算法的账户
+        (
+            "otpauth-migration://offline?data=Cg0KBkhlbGxvIRIBeCAE",
+            MigrationError::UnsupportedAlgorithm,
+        ),
+        // 未知的算法枚举值
+        (
+            "otpauth-migration://offline?data=Cg0KBkhlbGxvIRIBeCAJ",
+            MigrationError::InvalidData,
+        ),
+        // 未知的位数枚举值
+        (
+            "otpauth-migration://offline?data=Cg0KBkhlbGxvIRIBeCgD",
+            MigrationError::InvalidData,
+        ),
+        // 未知的类型枚举值
+        (
+            "otpauth-migration://offline?data=Cg0KBkhlbGxvIRIBeDAF",
+            MigrationError::InvalidData,
+        ),
+    ];
+    for (uri, expected) in cases {
+        assert_eq!(decode_migration_uri(uri).unwrap_err(), expected, "{}", uri);
+    }
+}
+
+fn accounts() -> Vec<OtpAuthUri> {
+    let sha256 = TotpConfig {
+        digits: 8,
+        hash_algorithm: HashAlgorithm::SHA256,
+        ..TotpConfig::default()
+    };
+    vec![
+        OtpAuthUri::new_totp(Some("ACME"), "alice", "JBSWY3DPEHPK3PXP", &sha256).unwrap(),
+        OtpAuthUri::new_totp(None, "carol", "GEZDGNBVGY3TQOJQ", &TotpConfig::default()).unwrap(),
+        OtpAuthUri::new_hotp(
+            None,
+            "bob",
+            "GEZDGNBVGY3TQOJQ",
+            42,
+            6,
+            HashAlgorithm::SHA512,
+        )
+        .unwrap(),
+    ]
+}
+
+/// 测试分批信息的解析
+#[test]
+fn test_decode_batch_info() {
+    let batch = decode_migration_batch(GOOGLE_EXPORT).unwrap();
+    assert_eq!(batch.version, 1);
+    assert_eq!(batch.batch_size, 1);
+    assert_eq!(batch.batch_index, 0);
+    assert_eq!(batch.batch_id, -1534807957);
+}
+
+/// 测试导出编码与按Protobuf格式独立计算的结果一致
+#[test]
+fn test_encode_single_account() {
+    let config = TotpConfig {
+        digits: 8,
+        hash_algorithm: HashAlgorithm::SHA256,
+        ..TotpConfig::default()
+    };
+    let account = OtpAuthUri::new_totp(Some("ACME"), "alice", "JBSWY3DPEHPK3PXP", &config).unwrap();
+    assert_eq!(
+        encode_migration_uris(&[account], 10, -5).unwrap(),
+        vec!["otpauth-migration://offline?data=Ch8KCkhlbGxvId6tvu8SBWFsaWNlGgRBQ01FIAIoAjACEAEYASAAKPv%2F%2F%2F%2F%2F%2F%2F%2F%2F%2FwE%3D"]
+    );
+}
+
+/// 测试多分批导出与乱序合并导入
+#[test]
+fn test_multi_part_round_trip() {
+    let accounts = accounts();
+    let uris = encode_migration_uris(&accounts, 2, 7).unwrap();
+    assert_eq!(uris.len(), 2);
+    for (index, uri) in uris.iter().enumerate() {
+        let batch = decode_migration_batch(uri).unwrap();
+        assert_eq!(batch.batch_size, 2);
+        assert_eq!(batch.batch_index, index as i32);
+        assert_eq!(batch.batch_id, 7);
+    }
+
+    let merged = decode_migration_uris(uris.iter().rev().map(String::as_str)).unwrap();
+    assert_eq!(merged, accounts);
+}
+
+/// 测试不完整、重复或混合批次的合并错误
+#[test]
+fn test_invalid_batches() {
+    let uris = encode_migration_uris(&accounts(), 2, 7).unwrap();
+    let other = encode_migration_uris(&accounts(), 2, 8).unwrap();
+    let cases = [
+        vec![uris[0].as_str()],
+        vec![uris[0].as_str(), uris[0].as_str()],
+        vec![uris[0].as_str(), other[1].as_str()],
+        vec![],
+    ];
+    for uris in cases {
+        assert_eq!(
+            decode_migration_uris(uris).unwrap_err(),
+            MigrationError::InvalidBatch
+        );
+    }
+    assert_eq!(
+        encode_migration_uris(&accounts(), 0, 7).unwrap_err(),
+        MigrationError::InvalidBatch
+    );
+}
+
+/// 测试迁移格式不支持的账户参数
+#[test]
+fn test_unsupported_export() {
+    let sm3 = TotpConfig {
+        hash_algorithm: HashAlgorithm::SM3,
+        ..TotpConfig::default()
+    };
+    let seven_digits = TotpConfig {
+        digits: 7,
+        ..TotpConfig::default()
+    };
+    let sixty_seconds = TotpConfig {
+        time_step: Duration::from_secs(60),
+        ..TotpConfig::default()
+    };
+    let cases = [
+        (sm3, MigrationError::UnsupportedAlgorithm),
+        (seven_digits, MigrationError::UnsupportedDigits),
+        (sixty_seconds, MigrationError::UnsupportedPeriod),
+    ];
+    for (config, expected) in cases {
+        let account = OtpAuthUri::new_totp(None, "alice", "JBSWY3DPEHPK3PXP", &config).unwrap();
+        assert_eq!(
+            encode_migration_uris(&[account], 10, 1).unwrap_err(),
+            expected
+        );
+    }
+}
@@ -0,0 +1 @@
+pub mod migration_test;
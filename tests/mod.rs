@@ -1 +1,17 @@
+#[cfg(feature = "std")]
+pub mod gm;
+#[cfg(feature = "std")]
+pub mod hotp;
+#[cfg(feature = "std")]
+pub mod migration;
+#[cfg(feature = "std")]
+pub mod ocra;
+#[cfg(feature = "qr")]
+pub mod qr;
+#[cfg(feature = "scan")]
+pub mod scan;
+#[cfg(feature = "std")]
+pub mod secret;
 pub mod totp;
+#[cfg(feature = "std")]
+pub mod uri;
@@ -0,0 +1 @@
+pub mod ocra_test;
@@ -0,0 +1,313 @@
+use totp_sm_rs::utils::ocra::ocra::{
+    generate_ocra, generate_ocra_with_clock, verify_ocra, verify_ocra_with_clock, OcraError,
+    OcraInput, OcraSuite, QuestionFormat,
+};
+use totp_sm_rs::utils::totp::clock::FixedClock;
+use totp_sm_rs::utils::totp::totp::{HashAlgorithm, TotpError};
+
+/// RFC 6287 附录C 的20、32、64字节测试密钥的Base32编码
+const KEY_20: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+const KEY_32: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA";
+const KEY_64: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNA";
+
+/// RFC 6287 附录C 使用的时间戳（0x132d0b6 分钟）
+const TIMESTAMP: i64 = 0x132d0b6 * 60;
+
+fn suite(suite: &str) -> OcraSuite {
+    suite.parse().unwrap()
+}
+
+/// 测试套件解析
+#[test]
+fn test_parse_suite() {
+    let parsed = suite("OCRA-1:HOTP-SHA256-8:C-QN08-PSHA1-S128-T1M");
+    assert_eq!(parsed.hash_algorithm(), HashAlgorithm::SHA256);
+    assert_eq!(parsed.digits(), 8);
+    assert!(parsed.counter());
+    assert_eq!(parsed.question_format(), QuestionFormat::Numeric);
+    assert_eq!(parsed.question_length(), 8);
+    assert_eq!(parsed.pin_hash(), Some(HashAlgorithm::SHA1));
+    assert_eq!(parsed.session_length(), Some(128));
+    assert_eq!(parsed.time_step(), Some(60));
+    assert_eq!(
+        parsed.to_string(),
+        "OCRA-1:HOTP-SHA256-8:C-QN08-PSHA1-S128-T1M"
+    );
+
+    let parsed = suite("OCRA-1:HOTP-SM3-6:QH10-PSM3");
+    assert_eq!(parsed.hash_algorithm(), HashAlgorithm::SM3);
+    assert_eq!(parsed.pin_hash(), Some(HashAlgorithm::SM3));
+}
+
+/// 测试无效套件
+#[test]
+fn test_invalid_suites() {
+    let invalid_suites = [
+        "OCRA-2:HOTP-SHA1-6:QN08",          // 不支持的版本
+        "OCRA-1:HOTP-MD5-6:QN08",           // 不支持的哈希算法
+        "OCRA-1:HOTP-sha1-6:QN08",          // 哈希算法名称须为大写
+        "OCRA-1:HOTPSHA1-6:QN08",           // 缺少分隔符
+        "OCRA-1:HOTP-WITHSHA1-6:QN08",      // 非RFC 6287写法
+        "OCRA-1:HOTP-SHA384-8:QN08",        // 仅支持SHA1、SHA256、SHA512与SM3
+        "OCRA-1:HOTP-SHA1-6:QN08-PSHA3256", // 不支持的PIN哈希算法
+        "OCRA-1:HOTP-SHA1-3:QN08",          // 无效的截取位数
+        "OCRA-1:HOTP-SHA1-6:C",             // 缺少挑战问题
+        "OCRA-1:HOTP-SHA1-6:QX08",          // 无效的问题格式
+        "OCRA-1:HOTP-SHA1-6:QN65",          // 问题长度超出范围
+        "OCRA-1:HOTP-SHA1-6:QN08-C",        // 输入顺序错误
+        "OCRA-1:HOTP-SHA1-6:QN08-T0M",      // 无效的时间步长
+        "OCRA-1:HOTP-SHA1-6",               // 缺少数据输入
+    ];
+    for invalid in invalid_suites {
+        assert!(
+            matches!(
+                invalid.parse::<OcraSuite>(),
+                Err(OcraError::InvalidSuite(_))
+            ),
+            "Expected invalid suite: {}",
+            invalid
+        );
+    }
+}
+
+/// 测试单向挑战应答（RFC 6287 附录C.1）
+#[test]
+fn test_one_way_challenge_response() {
+    let expected = [
+        "237653", "243178", "653583", "740991", "608993", "388898", "816933", "224598", "750600",
+        "294470",
+    ];
+    let ocra_suite = suite("OCRA-1:HOTP-SHA1-6:QN08");
+    for (i, code) in expected.iter().enumerate() {
+        let question = i.to_string().repeat(8);
+        let input = OcraInput {
+            question: Some(&question),
+            ..OcraInput::default()
+        };
+        assert_eq!(&generate_ocra(&ocra_suite, KEY_20, &input).unwrap(), code);
+    }
+
+    let expected = [
+        "65347737", "86775851", "78192410", "71565254", "10104329", "65983500", "70069104",
+        "91771096", "75011558", "08522129",
+    ];
+    let ocra_suite = suite("OCRA-1:HOTP-SHA256-8:C-QN08-PSHA1");
+    let pin_hash = ocra_suite.hash_pin("1234").unwrap();
+    for (counter, code) in expected.iter().enumerate() {
+        let input = OcraInput {
+            counter: Some(counter as u64),
+            question: Some("12345678"),
+            pin_hash: Some(&pin_hash),
+            ..OcraInput::default()
+        };
+        assert_eq!(&generate_ocra(&ocra_suite, KEY_32, &input).unwrap(), code);
+    }
+
+    let expected = ["83238735", "01501458", "17957585", "86776967", "86807031"];
+    let ocra_suite = suite("OCRA-1:HOTP-SHA256-8:QN08-PSHA1");
+    let pin_hash = ocra_suite.hash_pin("1234").unwrap();
+    for (i, code) in expected.iter().enumerate() {
+        let question = i.to_string().repeat(8);
+        let input = OcraInput {
+            question: Some(&question),
+            pin_hash: Some(&pin_hash),
+            ..OcraInput::default()
+        };
+        assert_eq!(&generate_ocra(&ocra_suite, KEY_32, &input).unwrap(), code);
+    }
+
+    let expected = [
+        "07016083", "63947962", "70123924", "25341727", "33203315", "34205738", "44343969",
+        "51946085", "20403879", "31409299",
+    ];
+    let ocra_suite = suite("OCRA-1:HOTP-SHA512-8:C-QN08");
+    for (i, code) in expected.iter().enumerate() {
+        let question = i.to_string().repeat(8);
+        let input = OcraInput {
+            counter: Some(i as u64),
+            question: Some(&question),
+            ..OcraInput::default()
+        };
+        assert_eq!(&generate_ocra(&ocra_suite, KEY_64, &input).unwrap(), code);
+    }
+
+    let expected = ["95209754", "55907591", "22048402", "24218844", "36209546"];
+    let ocra_suite = suite("OCRA-1:HOTP-SHA512-8:QN08-T1M");
+    for (i, code) in expected.iter().enumerate() {
+        let question = i.to_string().repeat(8);
+        let input = OcraInput {
+            question: Some(&question),
+            timestamp: Some(TIMESTAMP),
+            ..OcraInput::default()
+        };
+        assert_eq!(&generate_ocra(&ocra_suite, KEY_64, &input).unwrap(), code);
+    }
+}
+
+/// 测试双向挑战应答（RFC 6287 附录C.2）
+#[test]
+fn test_mutual_challenge_response() {
+    let cases = [
+        // (套件, 密钥, 是否包含PIN, 问题前缀, 问题后缀, 期望结果)
+        (
+            "OCRA-1:HOTP-SHA256-8:QA08",
+            KEY_32,
+            false,
+            "CLI2222",
+            "SRV1111",
+            ["28247970", "01984843", "65387857", "03351211", "83412541"],
+        ),
+        (
+            "OCRA-1:HOTP-SHA256-8:QA08",
+            KEY_32,
+            false,
+            "SRV1111",
+            "CLI2222",
+            ["15510767", "90175646", "33777207", "95285278", "28934924"],
+        ),
+        (
+            "OCRA-1:HOTP-SHA512-8:QA08",
+            KEY_64,
+            false,
+            "CLI2222",
+            "SRV1111",
+            ["79496648", "76831980", "12250499", "90856481", "12761449"],
+        ),
+        (
+            "OCRA-1:HOTP-SHA512-8:QA08-PSHA1",
+            KEY_64,
+            true,
+            "SRV1111",
+            "CLI2222",
+            ["18806276", "70020315", "01600026", "18951020", "32528969"],
+        ),
+    ];
+    for (suite_str, key, with_pin, first, second, expected) in cases {
+        let ocra_suite = suite(suite_str);
+        let pin_hash = ocra_suite.hash_pin("1234");
+        for (i, code) in expected.iter().enumerate() {
+            let question = format!("{}{}{}{}", first, i, second, i);
+            let input = OcraInput {
+                question: Some(&question),
+                pin_hash: pin_hash.as_deref().filter(|_| with_pin),
+                ..OcraInput::default()
+            };
+            assert_eq!(
+                &generate_ocra(&ocra_suite, key, &input).unwrap(),
+                code,
+                "suite {} question {}",
+                suite_str,
+                question
+            );
+        }
+    }
+}
+
+/// 测试交易签名（RFC 6287 附录C.3）
+#[test]
+fn test_plain_signature() {
+    let expected = ["53095496", "04110475", "31331128", "76028668", "46554205"];
+    let ocra_suite = suite("OCRA-1:HOTP-SHA256-8:QA08");
+    for (i, code) in expected.iter().enumerate() {
+        let question = format!("SIG1{}000", i);
+        let input = OcraInput {
+            question: Some(&question),
+            ..OcraInput::default()
+        };
+        assert_eq!(&generate_ocra(&ocra_suite, KEY_32, &input).unwrap(), code);
+    }
+
+    let expected = ["77537423", "31970405", "10235557", "95213541", "65360607"];
+    let ocra_suite = suite("OCRA-1:HOTP-SHA512-8:QA10-T1M");
+    for (i, code) in expected.iter().enumerate() {
+        let question = format!("SIG1{}00000", i);
+        let input = OcraInput {
+            question: Some(&question),
+            timestamp: Some(TIMESTAMP),
+            ..OcraInput::default()
+        };
+        assert_eq!(&generate_ocra(&ocra_suite, KEY_64, &input).unwrap(), code);
+    }
+}
+
+/// 测试校验及缺失输入
+#[test]
+fn test_verify_and_missing_inputs() {
+    let ocra_suite = suite("OCRA-1:HOTP-SHA256-8:C-QN08-PSHA1-S064");
+    let pin_hash = ocra_suite.hash_pin("1234").unwrap();
+    let input = OcraInput {
+        counter: Some(7),
+        question: Some("12345678"),
+        pin_hash: Some(&pin_hash),
+        session: Some(b"session"),
+        ..OcraInput::default()
+    };
+    let response = generate_ocra(&ocra_suite, KEY_32, &input).unwrap();
+    assert!(verify_ocra(&ocra_suite, KEY_32, &input, &response).is_ok());
+    assert!(matches!(
+        verify_ocra(&ocra_suite, KEY_32, &input, "00000000"),
+        Err(OcraError::Totp(TotpError::CodeMismatch))
+    ));
+
+    let missing_counter = OcraInput {
+        counter: None,
+        ..input.clone()
+    };
+    assert!(matches!(
+        generate_ocra(&ocra_suite, KEY_32, &missing_counter),
+        Err(OcraError::MissingInput("counter"))
+    ));
+
+    let wrong_pin = OcraInput {
+        pin_hash: Some(b"1234"),
+        ..input.clone()
+    };
+    assert!(matches!(
+        generate_ocra(&ocra_suite, KEY_32, &wrong_pin),
+        Err(OcraError::InvalidInput("pin_hash"))
+    ));
+
+    let bad_question = OcraInput {
+        question: Some("1234ABCD"),
+        ..input.clone()
+    };
+    assert!(matches!(
+        generate_ocra(&ocra_suite, KEY_32, &bad_question),
+        Err(OcraError::InvalidInput("question"))
+    ));
+
+    // QN08 最多允许两段8位挑战拼接
+    let long_question = OcraInput {
+        question: Some("12345678123456789"),
+        ..input
+    };
+    assert!(matches!(
+        generate_ocra(&ocra_suite, KEY_32, &long_question),
+        Err(OcraError::InvalidInput("question"))
+    ));
+}
+
+/// 测试使用指定时间源计算时间型套件（RFC 6287 附录C.1 QN08-T1M 向量）
+#[test]
+fn test_time_suite_with_clock() {
+    let ocra_suite = suite("OCRA-1:HOTP-SHA512-8:QN08-T1M");
+    let clock = FixedClock::from_unix(TIMESTAMP as u64);
+    let input = OcraInput {
+        question: Some("00000000"),
+        ..OcraInput::default()
+    };
+    assert_eq!(
+        generate_ocra_with_clock(&ocra_suite, KEY_64, &input, &clock).unwrap(),
+        "95209754"
+    );
+    assert!(verify_ocra_with_clock(&ocra_suite, KEY_64, &input, "95209754", &clock).is_ok());
+
+    // 输入中的时间戳优先于时间源
+    let later = FixedClock::from_unix(TIMESTAMP as u64 + 60);
+    assert!(verify_ocra_with_clock(&ocra_suite, KEY_64, &input, "95209754", &later).is_err());
+    let pinned = OcraInput {
+        timestamp: Some(TIMESTAMP),
+        ..input
+    };
+    assert!(verify_ocra_with_clock(&ocra_suite, KEY_64, &pinned, "95209754", &later).is_ok());
+}
@@ -0,0 +1 @@
+pub mod qr_test;
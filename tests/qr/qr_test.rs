@@ -0,0 +1,75 @@
+use totp_sm_rs::utils::qr::qr::{
+    render_png, render_svg, render_terminal, QrError, MAX_MODULE_SIZE,
+};
+use totp_sm_rs::utils::totp::totp::{HashAlgorithm, TotpConfig};
+use totp_sm_rs::utils::uri::uri::OtpAuthUri;
+
+fn enrollment_uri() -> OtpAuthUri {
+    let config = TotpConfig {
+        hash_algorithm: HashAlgorithm::SM3,
+        ..TotpConfig::default()
+    };
+    OtpAuthUri::new_totp(Some("ACME"), "alice", "JBSWY3DPEHPK3PXP", &config).unwrap()
+}
+
+/// 测试PNG渲染：图像尺寸、静区与定位图案
+#[test]
+fn test_render_png() {
+    let png_bytes = render_png(&enrollment_uri(), 4).unwrap();
+    let decoder = png::Decoder::new(png_bytes.as_slice());
+    let mut reader = decoder.read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    assert_eq!(info.width, info.height);
+    assert_eq!(info.color_type, png::ColorType::Grayscale);
+    // 边长为（模块数 + 2 * 4个静区模块）* 4像素，且二维码模块数为 4n + 17
+    assert_eq!(info.width % 4, 0);
+    assert_eq!((info.width / 4 - 8 - 17) % 4, 0);
+
+    let side = info.width as usize;
+    let pixel = |module_x: usize, module_y: usize| pixels[module_y * 4 * side + module_x * 4];
+    // 静区为白色，左上角定位图案的外框为黑色、内环为白色
+    assert_eq!(pixel(0, 0), 0xff);
+    assert_eq!(pixel(3, 3), 0xff);
+    assert_eq!(pixel(4, 4), 0);
+    assert_eq!(pixel(5, 5), 0xff);
+    assert_eq!(pixel(6, 6), 0);
+}
+
+/// 测试SVG与终端渲染
+#[test]
+fn test_render_svg_and_terminal() {
+    let svg = render_svg(&enrollment_uri(), 8).unwrap();
+    assert!(svg.starts_with("<?xml"));
+    assert!(svg.contains("<svg"));
+
+    let terminal = render_terminal(&enrollment_uri()).unwrap();
+    let lines: Vec<&str> = terminal.lines().collect();
+    assert!(lines.len() > 10);
+    assert!(lines
+        .iter()
+        .all(|line| line.chars().count() == lines[0].chars().count()));
+    assert!(terminal.contains('█') || terminal.contains('▀') || terminal.contains('▄'));
+}
+
+/// 测试无效及超出上限的模块尺寸
+#[test]
+fn test_invalid_module_size() {
+    assert_eq!(
+        render_png(&enrollment_uri(), 0).unwrap_err(),
+        QrError::InvalidModuleSize
+    );
+    assert_eq!(
+        render_svg(&enrollment_uri(), 0).unwrap_err(),
+        QrError::InvalidModuleSize
+    );
+    assert_eq!(
+        render_png(&enrollment_uri(), MAX_MODULE_SIZE + 1).unwrap_err(),
+        QrError::InvalidModuleSize
+    );
+    assert_eq!(
+        render_png(&enrollment_uri(), u32::MAX).unwrap_err(),
+        QrError::InvalidModuleSize
+    );
+    assert!(render_png(&enrollment_uri(), MAX_MODULE_SIZE).is_ok());
+}
@@ -0,0 +1 @@
+pub mod scan_test;
@@ -0,0 +1,84 @@
+use image::{GrayImage, ImageFormat};
+use std::io::Cursor;
+#[cfg(feature = "qr")]
+use totp_sm_rs::utils::qr::qr::render_png;
+use totp_sm_rs::utils::scan::scan::{scan_image, ScanError};
+#[cfg(feature = "qr")]
+use totp_sm_rs::utils::totp::totp::{generate_totp_code, HashAlgorithm, TotpConfig};
+#[cfg(feature = "qr")]
+use totp_sm_rs::utils::uri::uri::OtpAuthUri;
+
+#[cfg(feature = "qr")]
+fn totp_uri() -> OtpAuthUri {
+    let config = TotpConfig {
+        digits: 8,
+        hash_algorithm: HashAlgorithm::SM3,
+        ..TotpConfig::default()
+    };
+    OtpAuthUri::new_totp(Some("ACME"), "alice", "JBSWY3DPEHPK3PXP", &config).unwrap()
+}
+
+#[cfg(feature = "qr")]
+fn load(png_bytes: &[u8]) -> GrayImage {
+    image::load_from_memory(png_bytes).unwrap().to_luma8()
+}
+
+/// 测试扫描单个otpauth二维码并直接生成验证码
+#[cfg(feature = "qr")]
+#[test]
+fn test_scan_png() {
+    let uri = totp_uri();
+    let accounts = scan_image(&render_png(&uri, 4).unwrap()).unwrap();
+    assert_eq!(accounts, vec![uri]);
+
+    let config = TotpConfig {
+        timestamp: Some(1111111109),
+        ..accounts[0].totp_config().unwrap()
+    };
+    assert_eq!(
+        generate_totp_code(&accounts[0].secret, Some(config))
+            .unwrap()
+            .len(),
+        8
+    );
+}
+
+/// 测试从包含多个二维码的JPEG截图中导入全部账户
+#[cfg(feature = "qr")]
+#[test]
+fn test_scan_jpeg_with_multiple_codes() {
+    let first = load(&render_png(&totp_uri(), 4).unwrap());
+    let hotp =
+        OtpAuthUri::new_hotp(None, "bob", "GEZDGNBVGY3TQOJQ", 7, 6, HashAlgorithm::SHA1).unwrap();
+    let second = load(&render_png(&hotp, 4).unwrap());
+
+    let mut screenshot = GrayImage::from_pixel(
+        first.width() + second.width() + 40,
+        first.height().max(second.height()) + 40,
+        image::Luma([0xff]),
+    );
+    image::imageops::replace(&mut screenshot, &first, 20, 20);
+    image::imageops::replace(&mut screenshot, &second, first.width() as i64 + 20, 20);
+    let mut jpeg = Vec::new();
+    screenshot
+        .write_to(&mut Cursor::new(&mut jpeg), ImageFormat::Jpeg)
+        .unwrap();
+
+    let accounts = scan_image(&jpeg).unwrap();
+    assert_eq!(accounts.len(), 2);
+    assert!(accounts.contains(&totp_uri()));
+    assert!(accounts.contains(&hotp));
+}
+
+/// 测试无法解码的图片与不含二维码的图片
+#[test]
+fn test_scan_errors() {
+    assert!(matches!(scan_image(b"not an image"), Err(ScanError::Image)));
+
+    let blank = GrayImage::from_pixel(64, 64, image::Luma([0xff]));
+    let mut png_bytes = Vec::new();
+    blank
+        .write_to(&mut Cursor::new(&mut png_bytes), ImageFormat::Png)
+        .unwrap();
+    assert!(matches!(scan_image(&png_bytes), Err(ScanError::NoOtpCode)));
+}
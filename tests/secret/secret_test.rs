@@ -0,0 +1,244 @@
+use totp_sm_rs::utils::hotp::hotp::{generate_hotp_code, verify_hotp};
+use totp_sm_rs::utils::secret::secret::{
+    generate_secret, generate_secret_with_length, recommended_secret_length, Secret,
+    SecretEncoding, SecretError,
+};
+use totp_sm_rs::utils::totp::totp::{
+    generate_totp_code, verify_totp, HashAlgorithm, TotpConfig, TotpError,
+};
+
+/// 测试各哈希算法的推荐密钥长度及Base32编码
+#[test]
+fn test_generate_secret_lengths() {
+    let cases = [
+        (HashAlgorithm::SHA1, 20, 32),
+        (HashAlgorithm::SHA256, 32, 52),
+        (HashAlgorithm::SHA512, 64, 103),
+        (HashAlgorithm::SM3, 32, 52),
+    ];
+    for (hash_algorithm, length, base32_length) in cases {
+        assert_eq!(recommended_secret_length(hash_algorithm), length);
+        let secret = generate_secret(hash_algorithm).unwrap();
+        assert_eq!(secret.len(), length);
+        let encoded = secret.to_base32();
+        assert_eq!(encoded.len(), base32_length);
+        assert!(!encoded.contains('='));
+
+        // 生成的密钥可以直接用于开启安全检查的TOTP生成
+        let config = TotpConfig {
+            hash_algorithm,
+            is_check_security: true,
+            ..TotpConfig::default()
+        };
+        assert!(generate_totp_code(&encoded, Some(config)).is_ok());
+    }
+}
+
+/// 测试自定义长度及随机性
+#[test]
+fn test_generate_secret_with_length() {
+    let secret = generate_secret_with_length(40).unwrap();
+    assert_eq!(secret.as_bytes().len(), 40);
+    assert_ne!(generate_secret_with_length(40).unwrap(), secret);
+    assert!(matches!(
+        generate_secret_with_length(15),
+        Err(SecretError::InvalidLength)
+    ));
+}
+
+/// RFC 6238 附录B 的SHA1测试密钥 "12345678901234567890"
+const RFC_SECRET_BASE32: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+/// 测试从Base32、十六进制、Base64与原始字节构造密钥
+#[test]
+fn test_secret_constructors() {
+    let expected = Secret::from_bytes(b"12345678901234567890");
+    assert_eq!(Secret::from_base32(RFC_SECRET_BASE32).unwrap(), expected);
+    assert_eq!(
+        Secret::from_hex("3132333435363738393031323334353637383930").unwrap(),
+        expected
+    );
+    assert_eq!(
+        Secret::from_hex(
+            "3132333435363738393031323334353637383930"
+                .to_uppercase()
+                .as_str()
+        )
+        .unwrap(),
+        expected
+    );
+    assert_eq!(
+        Secret::from_base64("MTIzNDU2Nzg5MDEyMzQ1Njc4OTA=").unwrap(),
+        expected
+    );
+    assert_eq!(
+        Secret::from_base64("MTIzNDU2Nzg5MDEyMzQ1Njc4OTA").unwrap(),
+        expected
+    );
+    assert_eq!(Secret::from(b"12345678901234567890".to_vec()), expected);
+    assert_eq!(expected.to_base32(), RFC_SECRET_BASE32);
+
+    for invalid in ["", "0", "zz", "31 32", "+f"] {
+        assert_eq!(
+            Secret::from_hex(invalid).unwrap_err(),
+            SecretError::InvalidEncoding
+        );
+    }
+    assert_eq!(
+        Secret::from_base32("1!").unwrap_err(),
+        SecretError::InvalidEncoding
+    );
+    assert_eq!(
+        Secret::from_base64("").unwrap_err(),
+        SecretError::InvalidEncoding
+    );
+}
+
+/// 测试调试与显示输出不泄露密钥
+#[test]
+fn test_secret_redaction() {
+    let secret = Secret::from_base32(RFC_SECRET_BASE32).unwrap();
+    assert_eq!(format!("{:?}", secret), "Secret([REDACTED])");
+    assert_eq!(secret.to_string(), "[REDACTED]");
+}
+
+/// 测试各生成与校验接口均接受Secret，且结果与Base32字符串一致
+#[test]
+fn test_apis_accept_secret() {
+    let secret = Secret::from_hex("3132333435363738393031323334353637383930").unwrap();
+    let config = TotpConfig {
+        digits: 8,
+        timestamp: Some(59),
+        ..TotpConfig::default()
+    };
+    assert_eq!(
+        generate_totp_code(&secret, Some(config)).unwrap(),
+        "94287082"
+    );
+    assert_eq!(
+        generate_totp_code(RFC_SECRET_BASE32, Some(config)).unwrap(),
+        "94287082"
+    );
+    assert_eq!(
+        verify_totp(&secret, "94287082", Some(config), 0, 0).unwrap(),
+        0
+    );
+    assert_eq!(
+        generate_hotp_code(&secret, 0, 6, HashAlgorithm::SHA1).unwrap(),
+        "755224"
+    );
+    assert_eq!(
+        verify_hotp(&secret, "755224", 0, 6, HashAlgorithm::SHA1, 0).unwrap(),
+        1
+    );
+
+    // 空密钥在使用时被拒绝
+    assert!(matches!(
+        generate_totp_code(&Secret::from_bytes(&[]), None),
+        Err(TotpError::InvalidKeyLength)
+    ));
+}
+
+/// 测试宽松解析各种编码并报告识别出的编码
+#[test]
+fn test_parse_detects_encoding() {
+    let expected = Secret::from_bytes(b"12345678901234567890");
+    let cases = [
+        ("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ", SecretEncoding::Base32),
+        (
+            "gezd gnbv gy3t qojq gezd gnbv gy3t qojq",
+            SecretEncoding::Base32,
+        ),
+        (
+            "GEZDG-NBVGY-3TQOJ-QGEZD-GNBVG-Y3TQO-JQ",
+            SecretEncoding::Base32,
+        ),
+        (
+            "3132333435363738393031323334353637383930",
+            SecretEncoding::Hex,
+        ),
+        (
+            "0x3132333435363738393031323334353637383930",
+            SecretEncoding::Hex,
+        ),
+        ("MTIzNDU2Nzg5MDEyMzQ1Njc4OTA=", SecretEncoding::Base64),
+    ];
+    for (input, encoding) in cases {
+        let (secret, detected) = Secret::parse(input).unwrap();
+        assert_eq!(secret, expected, "{}", input);
+        assert_eq!(detected, encoding, "{}", input);
+    }
+
+    // 带填充的Base32
+    let (secret, detected) = Secret::parse("jbsw y3dp ehpk 3pxp gezd g===").unwrap();
+    assert_eq!(detected, SecretEncoding::Base32);
+    assert_eq!(secret.to_base32(), "JBSWY3DPEHPK3PXPGEZDG");
+}
+
+/// 测试解析错误指向非法字符在原始输入中的位置
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Secret::parse("JBSW Y3DP!EHPK"),
+        Err(SecretError::InvalidCharacter {
+            position: 9,
+            character: '!'
+        })
+    );
+    assert_eq!(
+        Secret::parse_as("jbsw y3d1", SecretEncoding::Base32),
+        Err(SecretError::InvalidCharacter {
+            position: 8,
+            character: '1'
+        })
+    );
+    assert_eq!(
+        Secret::parse_as("0x31 3g", SecretEncoding::Hex),
+        Err(SecretError::InvalidCharacter {
+            position: 6,
+            character: 'g'
+        })
+    );
+    assert_eq!(
+        Secret::parse_as("MTIz===", SecretEncoding::Base64),
+        Err(SecretError::InvalidCharacter {
+            position: 6,
+            character: '='
+        })
+    );
+
+    // 填充长度错误或为空时返回InvalidEncoding
+    assert_eq!(
+        Secret::parse("GEZDG====="),
+        Err(SecretError::InvalidEncoding)
+    );
+    assert_eq!(Secret::parse(" - "), Err(SecretError::InvalidEncoding));
+    assert_eq!(
+        Secret::parse_as("313", SecretEncoding::Hex),
+        Err(SecretError::InvalidEncoding)
+    );
+}
+
+/// 测试字符串密钥接口接受小写、分组与填充的Base32
+#[test]
+fn test_apis_accept_lenient_base32() {
+    let config = TotpConfig {
+        digits: 8,
+        ..TotpConfig::default()
+    };
+    assert_eq!(
+        generate_hotp_code(
+            "gezd gnbv gy3t qojq gezd gnbv gy3t qojq",
+            0,
+            6,
+            HashAlgorithm::SHA1
+        )
+        .unwrap(),
+        "755224"
+    );
+    assert!(generate_totp_code("jbsw y3dp ehpk 3pxp gezd g===", Some(config)).is_ok());
+    assert!(matches!(
+        generate_totp_code("GEZDG=====", None),
+        Err(TotpError::Base32DecodeError)
+    ));
+}
@@ -0,0 +1,170 @@
+use totp_sm_rs::utils::secret::secret::recommended_secret_length;
+use totp_sm_rs::utils::totp::generator::TotpGenerator;
+use totp_sm_rs::utils::totp::totp::{generate_totp_code, HashAlgorithm, TotpConfig};
+use totp_sm_rs::utils::uri::uri::OtpAuthUri;
+
+/// 测试密钥 "12345678901234567890123456789012" 的Base32编码
+const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA";
+
+/// 测试时间戳，与下列验证码一一对应
+const TIMESTAMPS: [i64; 4] = [59, 1111111109, 1234567890, 2000000000];
+
+/// SHA2、SHA3、BLAKE2的8位验证码，由Python hmac/hashlib计算交叉验证
+const SHA224_CODES: [&str; 4] = ["08784232", "35844743", "32974472", "88196405"];
+const SHA384_CODES: [&str; 4] = ["26760483", "03438124", "74831599", "58176115"];
+#[cfg(feature = "sha3")]
+const SHA3_CODES: [(HashAlgorithm, [&str; 4]); 4] = [
+    (
+        HashAlgorithm::SHA3_224,
+        ["36946527", "13216597", "97960937", "06829670"],
+    ),
+    (
+        HashAlgorithm::SHA3_256,
+        ["03503818", "00384900", "06893637", "49355738"],
+    ),
+    (
+        HashAlgorithm::SHA3_384,
+        ["66214681", "30811795", "25817905", "79006615"],
+    ),
+    (
+        HashAlgorithm::SHA3_512,
+        ["02747274", "79213086", "22354851", "69031959"],
+    ),
+];
+#[cfg(feature = "blake2")]
+const BLAKE2_CODES: [(HashAlgorithm, [&str; 4]); 2] = [
+    (
+        HashAlgorithm::BLAKE2B,
+        ["13203303", "55622833", "17469048", "92347868"],
+    ),
+    (
+        HashAlgorithm::BLAKE2S,
+        ["91948900", "68091993", "96504322", "28968055"],
+    ),
+];
+
+/// Streebog的8位验证码，所用HMAC实现由 test_streebog_hmac_rfc7836 以 RFC 7836 的示例校验
+#[cfg(feature = "streebog")]
+const STREEBOG_CODES: [(HashAlgorithm, [&str; 4]); 2] = [
+    (
+        HashAlgorithm::STREEBOG256,
+        ["06346470", "55290409", "14096368", "79945841"],
+    ),
+    (
+        HashAlgorithm::STREEBOG512,
+        ["17715401", "95647954", "13570995", "11067356"],
+    ),
+];
+
+fn config(hash_algorithm: HashAlgorithm, timestamp: Option<i64>) -> Option<TotpConfig> {
+    Some(TotpConfig {
+        digits: 8,
+        timestamp,
+        hash_algorithm,
+        ..TotpConfig::default()
+    })
+}
+
+/// 校验逐次调用接口与预先载入密钥的生成器均得到期望的验证码
+fn assert_codes(hash_algorithm: HashAlgorithm, codes: [&str; 4]) {
+    let generator = TotpGenerator::new(SECRET, config(hash_algorithm, None)).unwrap();
+    for (timestamp, expected) in TIMESTAMPS.iter().zip(codes) {
+        assert_eq!(
+            generate_totp_code(SECRET, config(hash_algorithm, Some(*timestamp))).unwrap(),
+            expected,
+            "{} at {}",
+            hash_algorithm,
+            timestamp
+        );
+        assert_eq!(generator.code_at(timestamp / 30), expected);
+    }
+}
+
+/// 测试SHA224与SHA384
+#[test]
+fn test_sha224_sha384() {
+    assert_codes(HashAlgorithm::SHA224, SHA224_CODES);
+    assert_codes(HashAlgorithm::SHA384, SHA384_CODES);
+}
+
+/// 测试SHA3系列
+#[cfg(feature = "sha3")]
+#[test]
+fn test_sha3() {
+    for (hash_algorithm, codes) in SHA3_CODES {
+        assert_codes(hash_algorithm, codes);
+    }
+}
+
+/// 测试BLAKE2b与BLAKE2s
+#[cfg(feature = "blake2")]
+#[test]
+fn test_blake2() {
+    for (hash_algorithm, codes) in BLAKE2_CODES {
+        assert_codes(hash_algorithm, codes);
+    }
+}
+
+/// 测试Streebog
+#[cfg(feature = "streebog")]
+#[test]
+fn test_streebog() {
+    for (hash_algorithm, codes) in STREEBOG_CODES {
+        assert_codes(hash_algorithm, codes);
+    }
+}
+
+/// 测试Streebog的HMAC实现符合 RFC 7836 中 HMAC_GOSTR3411_2012_256/512 的示例
+#[cfg(feature = "streebog")]
+#[test]
+fn test_streebog_hmac_rfc7836() {
+    use hmac::{Hmac, Mac};
+
+    fn hmac<M: Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> String {
+        let mut mac = <M as Mac>::new_from_slice(key).unwrap();
+        mac.update(message);
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    let key: Vec<u8> = (0x00..=0x1f).collect();
+    let message = [
+        0x01, 0x26, 0xbd, 0xb8, 0x78, 0x00, 0xaf, 0x21, 0x43, 0x41, 0x45, 0x65, 0x63, 0x78, 0x01,
+        0x00,
+    ];
+    assert_eq!(
+        hmac::<Hmac<streebog::Streebog256>>(&key, &message),
+        "a1aa5f7de402d7b3d323f2991c8d4534013137010a83754fd0af6d7cd4922ed9"
+    );
+    assert_eq!(
+        hmac::<Hmac<streebog::Streebog512>>(&key, &message),
+        "a59bab22ecae19c65fbde6e5f4e9f5d8549d31f037f9df9b905500e171923a773d5f1530f2ed7e964cb2eedc29e9ad2f3afe93b2814f79f5000ffc0366c251e6"
+    );
+}
+
+/// 测试算法名称、摘要长度及otpauth URI往返转换
+#[test]
+fn test_names_and_uri_round_trip() {
+    assert_eq!(HashAlgorithm::SHA224.to_string(), "SHA224");
+    assert_eq!(HashAlgorithm::SHA384.output_size(), 48);
+    assert_eq!(recommended_secret_length(HashAlgorithm::SHA384), 48);
+    #[cfg(feature = "sha3")]
+    assert_eq!(HashAlgorithm::SHA3_256.to_string(), "SHA3-256");
+    #[cfg(feature = "streebog")]
+    assert_eq!(HashAlgorithm::STREEBOG512.output_size(), 64);
+
+    for &hash_algorithm in HashAlgorithm::ALL {
+        let uri = format!(
+            "otpauth://totp/Example:alice?secret={}&issuer=Example&algorithm={}",
+            SECRET, hash_algorithm
+        );
+        let parsed: OtpAuthUri = uri.parse().unwrap();
+        assert_eq!(parsed.algorithm, hash_algorithm);
+        assert!(parsed
+            .to_string()
+            .contains(&format!("algorithm={}", hash_algorithm)));
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+use std::time::Duration;
+use totp_sm_rs::utils::totp::clock::{Clock, FixedClock, MockClock, OffsetClock, SystemClock};
+use totp_sm_rs::utils::totp::replay::{verify_totp_once_with_clock, InMemoryReplayStore};
+use totp_sm_rs::utils::totp::totp::{
+    generate_totp_code_with_clock, verify_totp_with_clock, TotpConfig, TotpError,
+};
+
+const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+fn config() -> Option<TotpConfig> {
+    Some(TotpConfig {
+        digits: 8,
+        ..TotpConfig::default()
+    })
+}
+
+/// 测试固定时钟与偏移时钟生成RFC 6238测试向量
+#[test]
+fn test_fixed_and_offset_clock() {
+    let clock = FixedClock::from_unix(59);
+    assert_eq!(
+        generate_totp_code_with_clock(SECRET, config(), &clock).unwrap(),
+        "94287082"
+    );
+
+    let ahead = OffsetClock::ahead(FixedClock::from_unix(1111111000), Duration::from_secs(109));
+    assert_eq!(
+        generate_totp_code_with_clock(SECRET, config(), &ahead).unwrap(),
+        "07081804"
+    );
+    let behind = OffsetClock::behind(FixedClock::from_unix(1111111200), Duration::from_secs(91));
+    assert_eq!(ahead.now(), behind.now());
+
+    // 落后的偏移不会早于Unix纪元
+    let behind = OffsetClock::behind(FixedClock::from_unix(10), Duration::from_secs(20));
+    assert_eq!(behind.now(), Duration::ZERO);
+}
+
+/// 测试配置中的自定义时间戳优先于时钟
+#[test]
+fn test_timestamp_overrides_clock() {
+    let config = Some(TotpConfig {
+        digits: 8,
+        timestamp: Some(59),
+        ..TotpConfig::default()
+    });
+    let clock = FixedClock::from_unix(1234567890);
+    assert_eq!(
+        generate_totp_code_with_clock(SECRET, config, &clock).unwrap(),
+        "94287082"
+    );
+}
+
+/// 测试模拟时钟快进时间窗口
+#[test]
+fn test_mock_clock_fast_forward() {
+    let clock = Arc::new(MockClock::from_unix(59));
+    let code = generate_totp_code_with_clock(SECRET, config(), &clock).unwrap();
+    assert_eq!(
+        verify_totp_with_clock(SECRET, &code, config(), 1, 0, &clock).unwrap(),
+        0
+    );
+
+    // 前进一个时间步后仍在回溯窗口内，再前进一步则超出窗口
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(
+        verify_totp_with_clock(SECRET, &code, config(), 1, 0, &clock).unwrap(),
+        -1
+    );
+    clock.advance(Duration::from_secs(30));
+    assert!(matches!(
+        verify_totp_with_clock(SECRET, &code, config(), 1, 0, &clock),
+        Err(TotpError::CodeMismatch)
+    ));
+
+    clock.set(Duration::from_secs(1111111109));
+    assert_eq!(
+        generate_totp_code_with_clock(SECRET, config(), &*clock).unwrap(),
+        "07081804"
+    );
+}
+
+/// 测试防重放校验使用注入的时钟
+#[test]
+fn test_replay_with_clock() {
+    let clock = MockClock::from_unix(59);
+    let store = InMemoryReplayStore::new();
+    assert_eq!(
+        verify_totp_once_with_clock(SECRET, "94287082", config(), 0, 0, "alice", &store, &clock)
+            .unwrap(),
+        0
+    );
+    assert!(matches!(
+        verify_totp_once_with_clock(SECRET, "94287082", config(), 1, 0, "alice", &store, &clock),
+        Err(TotpError::ReplayedCode)
+    ));
+}
+
+/// 测试系统时钟返回当前时间
+#[test]
+fn test_system_clock() {
+    assert!(SystemClock.now() > Duration::from_secs(1_700_000_000));
+}
+
+/// 测试chrono的UTC时间作为固定时间源
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_clock() {
+    let time = chrono::DateTime::from_timestamp(59, 0).unwrap();
+    assert_eq!(time.now(), Duration::from_secs(59));
+    assert_eq!(
+        generate_totp_code_with_clock(SECRET, config(), &time).unwrap(),
+        "94287082"
+    );
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+use totp_sm_rs::utils::totp::clock::MockClock;
+use totp_sm_rs::utils::totp::code::{generate_totp, generate_totp_with_clock};
+use totp_sm_rs::utils::totp::totp::{generate_totp_code, TotpConfig, TotpError};
+
+const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+fn config() -> Option<TotpConfig> {
+    Some(TotpConfig {
+        digits: 8,
+        ..TotpConfig::default()
+    })
+}
+
+/// 测试生成结果包含时间步计数、有效窗口与剩余时间
+#[test]
+fn test_code_window() {
+    let clock = MockClock::new(Duration::from_millis(1_111_111_109_250));
+    let code = generate_totp_with_clock(SECRET, config(), &clock).unwrap();
+    assert_eq!(code.code(), "07081804");
+    assert_eq!(code.to_string(), "07081804");
+    assert_eq!(code.counter(), 37037036);
+    assert_eq!(code.valid_from(), Duration::from_secs(1111111080));
+    assert_eq!(code.valid_until(), Duration::from_secs(1111111110));
+    assert_eq!(code.remaining(), Duration::from_millis(750));
+    assert_eq!(code.remaining_secs(), 1);
+
+    // 窗口开始时剩余时间为完整的时间步长
+    clock.set(Duration::from_secs(1111111080));
+    let code = generate_totp_with_clock(SECRET, config(), &clock).unwrap();
+    assert_eq!(code.remaining_secs(), 30);
+}
+
+/// 测试相邻时间步的验证码
+#[test]
+fn test_adjacent_codes() {
+    let clock = MockClock::from_unix(1111111109);
+    let code = generate_totp_with_clock(SECRET, config(), &clock).unwrap();
+    let next = code.next(SECRET).unwrap();
+    assert_eq!(next.code(), "14050471");
+    assert_eq!(next.counter(), code.counter() + 1);
+    assert_eq!(next.valid_from(), code.valid_until());
+    assert_eq!(next.remaining(), Duration::from_secs(31));
+
+    let previous = code.previous(SECRET).unwrap();
+    assert_eq!(previous.valid_until(), code.valid_from());
+    assert_eq!(previous.remaining(), Duration::ZERO);
+    assert_eq!(previous.next(SECRET).unwrap().code(), code.code());
+
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(
+        generate_totp_with_clock(SECRET, config(), &clock)
+            .unwrap()
+            .code(),
+        next.code()
+    );
+}
+
+/// 测试自定义T0与时间步长下的窗口，以及与字符串接口结果一致
+#[test]
+fn test_window_with_t0() {
+    let config = TotpConfig::builder()
+        .time_step(Duration::from_secs(3600))
+        .t0(100)
+        .timestamp(7300)
+        .build()
+        .unwrap();
+    let code = generate_totp(SECRET, Some(config)).unwrap();
+    assert_eq!(code.counter(), 2);
+    assert_eq!(code.valid_from(), Duration::from_secs(7300));
+    assert_eq!(code.valid_until(), Duration::from_secs(10900));
+    assert_eq!(code.remaining_secs(), 3600);
+    assert_eq!(
+        code.code(),
+        generate_totp_code(SECRET, Some(config)).unwrap()
+    );
+}
+
+/// 测试T0所在时间步没有上一个验证码，以及偏移超出范围
+#[test]
+fn test_previous_at_t0() {
+    let config = TotpConfig::builder()
+        .t0(100)
+        .timestamp(110)
+        .build()
+        .unwrap();
+    let code = generate_totp(SECRET, Some(config)).unwrap();
+    assert_eq!(code.counter(), 0);
+    assert!(matches!(
+        code.previous(SECRET),
+        Err(TotpError::InvalidTimestamp)
+    ));
+    assert!(matches!(
+        code.at_offset(SECRET, i64::MAX)
+            .unwrap()
+            .at_offset(SECRET, 1),
+        Err(TotpError::InvalidTimestamp)
+    ));
+    assert_eq!(
+        code.next(SECRET).unwrap().previous(SECRET).unwrap().code(),
+        code.code()
+    );
+}
@@ -0,0 +1,101 @@
+use sha1::Sha1;
+use sha2::digest::consts::U16;
+use sha2::digest::core_api::{CoreWrapper, CtVariableCoreWrapper};
+use sha2::{Sha256, Sha256VarCore, Sha512, Sha512_256};
+use sm3::Sm3;
+use totp_sm_rs::utils::hotp::hotp::{hotp_value, hotp_value_with_digest};
+use totp_sm_rs::utils::secret::secret::Secret;
+use totp_sm_rs::utils::totp::clock::FixedClock;
+use totp_sm_rs::utils::totp::totp::{
+    generate_totp_code, generate_totp_code_with_digest, verify_totp_with_digest, HashAlgorithm,
+    TotpConfig, TotpError,
+};
+
+/// RFC 6238 附录B SHA256测试密钥
+const SECRET: &[u8] = b"12345678901234567890123456789012";
+
+/// 截断为16字节输出的SHA256，长度不足以进行动态截取
+type Sha256Trunc16 = CoreWrapper<CtVariableCoreWrapper<Sha256VarCore, U16>>;
+
+fn config(hash_algorithm: HashAlgorithm, timestamp: Option<i64>) -> Option<TotpConfig> {
+    Some(TotpConfig {
+        digits: 8,
+        timestamp,
+        hash_algorithm,
+        ..TotpConfig::default()
+    })
+}
+
+/// 测试泛型摘要接口与哈希算法枚举的结果一致
+#[test]
+fn test_matches_hash_algorithm() {
+    let secret = Secret::from_bytes(SECRET);
+    let clock = FixedClock::from_unix(0);
+    for timestamp in [59, 1111111109, 1234567890, 20000000000] {
+        let generate = |hash_algorithm| {
+            generate_totp_code(&secret, config(hash_algorithm, Some(timestamp))).unwrap()
+        };
+        let config = config(HashAlgorithm::SHA1, Some(timestamp));
+        assert_eq!(
+            generate_totp_code_with_digest::<Sha1>(&secret, config, &clock).unwrap(),
+            generate(HashAlgorithm::SHA1)
+        );
+        assert_eq!(
+            generate_totp_code_with_digest::<Sha256>(&secret, config, &clock).unwrap(),
+            generate(HashAlgorithm::SHA256)
+        );
+        assert_eq!(
+            generate_totp_code_with_digest::<Sha512>(&secret, config, &clock).unwrap(),
+            generate(HashAlgorithm::SHA512)
+        );
+        assert_eq!(
+            generate_totp_code_with_digest::<Sm3>(&secret, config, &clock).unwrap(),
+            generate(HashAlgorithm::SM3)
+        );
+    }
+    for counter in 0..10 {
+        assert_eq!(
+            hotp_value_with_digest::<Sm3>(SECRET, counter, 6).unwrap(),
+            hotp_value(SECRET, counter, 6, HashAlgorithm::SM3).unwrap()
+        );
+    }
+}
+
+/// 测试枚举之外的摘要算法（SHA-512/256，结果与Python hmac交叉验证）
+#[test]
+fn test_custom_digest() {
+    let secret = Secret::from_bytes(SECRET);
+    let config = config(HashAlgorithm::SHA1, None);
+    let vectors = [(59, "00441233"), (1111111109, "97406494")];
+    for (timestamp, expected) in vectors {
+        let clock = FixedClock::from_unix(timestamp);
+        assert_eq!(
+            generate_totp_code_with_digest::<Sha512_256>(&secret, config, &clock).unwrap(),
+            expected
+        );
+    }
+
+    // 前一个时间步的验证码在回溯窗口内
+    let clock = FixedClock::from_unix(1111111109 + 30);
+    assert_eq!(
+        verify_totp_with_digest::<Sha512_256>(&secret, "97406494", config, 1, 0, &clock).unwrap(),
+        -1
+    );
+    assert!(matches!(
+        verify_totp_with_digest::<Sha512_256>(&secret, "97406494", config, 0, 1, &clock),
+        Err(TotpError::CodeMismatch)
+    ));
+}
+
+/// 测试输出不足20字节的摘要算法被拒绝
+#[test]
+fn test_short_digest_rejected() {
+    assert!(matches!(
+        hotp_value_with_digest::<Sha256Trunc16>(SECRET, 0, 6),
+        Err(TotpError::UnsupportedDigest)
+    ));
+    assert!(matches!(
+        hotp_value_with_digest::<Sha256>(SECRET, 0, 11),
+        Err(TotpError::InvalidDigits)
+    ));
+}
@@ -0,0 +1,195 @@
+use std::time::Duration;
+use totp_sm_rs::utils::totp::clock::{FixedClock, MockClock};
+use totp_sm_rs::utils::totp::drift::DriftTracker;
+use totp_sm_rs::utils::totp::totp::{generate_totp_code, TotpConfig, TotpError};
+
+const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+const NOW: i64 = 1234567890;
+
+fn config_at(timestamp: i64) -> TotpConfig {
+    TotpConfig {
+        digits: 8,
+        timestamp: Some(timestamp),
+        ..TotpConfig::default()
+    }
+}
+
+/// 生成相对当前时间偏移若干时间步的验证码
+fn code_at_step(offset: i64) -> String {
+    generate_totp_code(SECRET, Some(config_at(NOW + offset * 30))).unwrap()
+}
+
+/// 测试校验成功后学习漂移
+#[test]
+fn test_verify_learns_drift() {
+    let tracker = DriftTracker::new();
+    assert_eq!(tracker.drift("alice"), 0);
+
+    let drift = tracker
+        .verify(
+            SECRET,
+            &code_at_step(1),
+            Some(config_at(NOW)),
+            1,
+            1,
+            "alice",
+        )
+        .unwrap();
+    assert_eq!(drift, 1);
+    assert_eq!(tracker.drift("alice"), 1);
+
+    // 窗口以已学习的漂移为中心，偏移2个时间步的验证码现在可以被接受
+    let drift = tracker
+        .verify(
+            SECRET,
+            &code_at_step(2),
+            Some(config_at(NOW)),
+            1,
+            1,
+            "alice",
+        )
+        .unwrap();
+    assert_eq!(drift, 2);
+
+    // 其他凭据不受影响
+    let result = tracker.verify(SECRET, &code_at_step(2), Some(config_at(NOW)), 1, 1, "bob");
+    assert!(matches!(result, Err(TotpError::CodeMismatch)));
+}
+
+/// 测试连续两个验证码的重新同步
+#[test]
+fn test_resync_far_outside_window() {
+    let tracker = DriftTracker::new();
+    let result = tracker.verify(
+        SECRET,
+        &code_at_step(-40),
+        Some(config_at(NOW)),
+        1,
+        1,
+        "alice",
+    );
+    assert!(matches!(result, Err(TotpError::CodeMismatch)));
+
+    let drift = tracker
+        .resync(
+            SECRET,
+            &code_at_step(-40),
+            &code_at_step(-39),
+            Some(config_at(NOW)),
+            100,
+            "alice",
+        )
+        .unwrap();
+    assert_eq!(drift, -39);
+    assert_eq!(tracker.drift("alice"), -39);
+
+    // 同步后常规窗口即可接受漂移后的验证码
+    let drift = tracker
+        .verify(
+            SECRET,
+            &code_at_step(-38),
+            Some(config_at(NOW)),
+            1,
+            1,
+            "alice",
+        )
+        .unwrap();
+    assert_eq!(drift, -38);
+}
+
+/// 测试非连续验证码无法重新同步
+#[test]
+fn test_resync_requires_consecutive_codes() {
+    let tracker = DriftTracker::new();
+    tracker.set_drift("alice", 3);
+    let result = tracker.resync(
+        SECRET,
+        &code_at_step(-40),
+        &code_at_step(-38),
+        Some(config_at(NOW)),
+        100,
+        "alice",
+    );
+    assert!(matches!(result, Err(TotpError::CodeMismatch)));
+    assert_eq!(tracker.drift("alice"), 3);
+
+    // 超出搜索范围的验证码同样无法同步
+    let result = tracker.resync(
+        SECRET,
+        &code_at_step(-40),
+        &code_at_step(-39),
+        Some(config_at(NOW)),
+        20,
+        "alice",
+    );
+    assert!(matches!(result, Err(TotpError::CodeMismatch)));
+}
+
+/// 测试使用指定时间源学习漂移与重新同步，时钟推进后漂移估计保持不变
+#[test]
+fn test_drift_with_clock() {
+    let tracker = DriftTracker::new();
+    let clock = MockClock::new(Duration::from_secs(NOW as u64));
+    let config = TotpConfig {
+        digits: 8,
+        ..TotpConfig::default()
+    };
+
+    let drift = tracker
+        .resync_with_clock(
+            SECRET,
+            &code_at_step(4),
+            &code_at_step(5),
+            Some(config),
+            10,
+            "carol",
+            &clock,
+        )
+        .unwrap();
+    assert_eq!(drift, 5);
+
+    clock.advance(Duration::from_secs(60));
+    let drift = tracker
+        .verify_with_clock(
+            SECRET,
+            &code_at_step(7),
+            Some(config),
+            0,
+            0,
+            "carol",
+            &clock,
+        )
+        .unwrap();
+    assert_eq!(drift, 5);
+
+    // 第二个验证码超出max_drift时不接受（时钟已推进2个时间步）
+    let result = tracker.resync_with_clock(
+        SECRET,
+        &code_at_step(7),
+        &code_at_step(8),
+        Some(config),
+        5,
+        "dave",
+        &clock,
+    );
+    assert!(matches!(result, Err(TotpError::CodeMismatch)));
+}
+
+/// 测试T0附近重新同步不搜索负数时间步
+#[test]
+fn test_resync_near_t0() {
+    let tracker = DriftTracker::new();
+    let clock = FixedClock::from_unix(30);
+    let config = TotpConfig {
+        digits: 8,
+        ..TotpConfig::default()
+    };
+    let first = generate_totp_code(SECRET, Some(config_at(0))).unwrap();
+    let second = generate_totp_code(SECRET, Some(config_at(30))).unwrap();
+    assert_eq!(
+        tracker
+            .resync_with_clock(SECRET, &first, &second, Some(config), 10, "erin", &clock)
+            .unwrap(),
+        0
+    );
+}
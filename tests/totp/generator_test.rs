@@ -0,0 +1,123 @@
+use totp_sm_rs::utils::secret::secret::Secret;
+use totp_sm_rs::utils::totp::clock::FixedClock;
+use totp_sm_rs::utils::totp::generator::TotpGenerator;
+use totp_sm_rs::utils::totp::totp::{
+    generate_totp_code, verify_totp, HashAlgorithm, TotpConfig, TotpError,
+};
+
+const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+fn config(hash_algorithm: HashAlgorithm, timestamp: Option<i64>) -> Option<TotpConfig> {
+    Some(TotpConfig {
+        digits: 8,
+        timestamp,
+        hash_algorithm,
+        ..TotpConfig::default()
+    })
+}
+
+/// 测试生成器与逐次调用接口的结果一致（各哈希算法）
+#[test]
+fn test_matches_generate_totp_code() {
+    let algorithms = [
+        HashAlgorithm::SHA1,
+        HashAlgorithm::SHA256,
+        HashAlgorithm::SHA512,
+        HashAlgorithm::SM3,
+    ];
+    for hash_algorithm in algorithms {
+        let generator = TotpGenerator::new(SECRET, config(hash_algorithm, None)).unwrap();
+        for timestamp in [59, 1111111109, 1234567890, 20000000000] {
+            let expected =
+                generate_totp_code(SECRET, config(hash_algorithm, Some(timestamp))).unwrap();
+            assert_eq!(generator.code_at(timestamp / 30), expected);
+            assert_eq!(
+                generator
+                    .generate_with_clock(&FixedClock::from_unix(timestamp as u64))
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+}
+
+/// 测试验证码数值保留前导零语义
+#[test]
+fn test_code_value_and_padding() {
+    let generator = TotpGenerator::new(SECRET, config(HashAlgorithm::SHA1, None)).unwrap();
+    // RFC 6238 测试向量 07081804（T=1111111109）
+    assert_eq!(generator.code_value(37037036).value(), 7081804);
+    assert_eq!(generator.code_value(37037036).digits(), 8);
+    assert_eq!(generator.code_at(37037036), "07081804");
+}
+
+/// 测试生成器校验与窗口偏移
+#[test]
+fn test_verify() {
+    let generator =
+        TotpGenerator::new(SECRET, config(HashAlgorithm::SHA1, Some(1111111109))).unwrap();
+    assert_eq!(generator.verify("07081804", 0, 0).unwrap(), 0);
+    assert_eq!(generator.verify("14050471", 1, 1).unwrap(), 1);
+    assert!(matches!(
+        generator.verify("14050471", 1, 0),
+        Err(TotpError::CodeMismatch)
+    ));
+    assert!(matches!(
+        generator.verify("7081804", 0, 0),
+        Err(TotpError::CodeMismatch)
+    ));
+    assert_eq!(
+        generator.verify("07081804", 0, 0).unwrap(),
+        verify_totp(
+            SECRET,
+            "07081804",
+            config(HashAlgorithm::SHA1, Some(1111111109)),
+            0,
+            0
+        )
+        .unwrap()
+    );
+}
+
+/// 测试创建时校验配置与密钥
+#[test]
+fn test_new_validation() {
+    assert!(matches!(
+        TotpGenerator::new("12345", None),
+        Err(TotpError::Base32DecodeError)
+    ));
+    assert!(matches!(
+        TotpGenerator::new(
+            SECRET,
+            Some(TotpConfig {
+                digits: 11,
+                ..TotpConfig::default()
+            })
+        ),
+        Err(TotpError::InvalidDigits)
+    ));
+    let short = Secret::from_bytes(b"short");
+    assert!(matches!(
+        TotpGenerator::new(
+            &short,
+            Some(TotpConfig {
+                is_check_security: true,
+                ..TotpConfig::default()
+            })
+        ),
+        Err(TotpError::InvalidKeyLength)
+    ));
+    assert!(TotpGenerator::new(&short, None).is_ok());
+}
+
+/// 测试释放生成器时清零的只是自身的密钥状态，克隆出的生成器不受影响（各哈希算法）
+#[test]
+fn test_clone_survives_drop() {
+    for &hash_algorithm in HashAlgorithm::ALL {
+        let generator = TotpGenerator::new(SECRET, config(hash_algorithm, None)).unwrap();
+        let expected = generator.code_at(37037036);
+        let clone = generator.clone();
+        drop(generator);
+        assert_eq!(clone.code_at(37037036), expected, "{}", hash_algorithm);
+    }
+}
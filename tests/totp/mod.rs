@@ -1 +1,20 @@
+#[cfg(feature = "std")]
+pub mod algorithm_test;
+#[cfg(feature = "std")]
+pub mod clock_test;
+#[cfg(feature = "std")]
+pub mod code_test;
+#[cfg(feature = "std")]
+pub mod digest_test;
+#[cfg(feature = "std")]
+pub mod drift_test;
+#[cfg(feature = "std")]
+pub mod generator_test;
+pub mod no_alloc_test;
+pub mod parse_test;
+#[cfg(feature = "std")]
+pub mod replay_test;
+#[cfg(feature = "tokio")]
+pub mod stream_test;
+#[cfg(feature = "std")]
 pub mod totp_test;
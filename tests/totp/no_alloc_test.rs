@@ -0,0 +1,145 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use totp_sm_rs::utils::hotp::hotp::{hotp_value, write_hotp_code, MAX_DIGITS};
+use totp_sm_rs::utils::totp::clock::FixedClock;
+use totp_sm_rs::utils::totp::generator::TotpGenerator;
+use totp_sm_rs::utils::totp::totp::{
+    totp_value, write_totp_code, HashAlgorithm, TotpConfig, TotpError,
+};
+
+/// RFC 6238 附录B SHA1测试密钥
+const SECRET: &[u8] = b"12345678901234567890";
+
+/// 按线程统计堆分配次数的分配器
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// 统计闭包执行期间当前线程的堆分配次数
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+fn config(hash_algorithm: HashAlgorithm) -> TotpConfig {
+    TotpConfig {
+        digits: 8,
+        hash_algorithm,
+        ..TotpConfig::default()
+    }
+}
+
+/// 测试写入调用方缓冲区的TOTP验证码符合RFC 6238且不进行堆分配
+#[test]
+fn test_write_totp_code_without_allocation() {
+    let clock = FixedClock::from_unix(1111111109);
+    let config = config(HashAlgorithm::SHA1);
+    let mut buf = [0u8; 8];
+    let (written, count) =
+        allocations(|| write_totp_code(SECRET, &config, &clock, &mut buf).map(str::len));
+    assert_eq!(count, 0);
+    assert_eq!(written.unwrap(), 8);
+    assert_eq!(&buf, b"07081804");
+
+    let (value, count) = allocations(|| totp_value(SECRET, &config, &clock).unwrap());
+    assert_eq!(count, 0);
+    assert_eq!(value.value(), 7081804);
+    assert_eq!(value.digits(), 8);
+    assert_eq!(value.to_string(), "07081804");
+}
+
+/// 测试各哈希算法的HOTP数值接口不进行堆分配
+#[test]
+fn test_hotp_value_without_allocation() {
+    let algorithms = [
+        HashAlgorithm::SHA1,
+        HashAlgorithm::SHA256,
+        HashAlgorithm::SHA512,
+        HashAlgorithm::SM3,
+    ];
+    for hash_algorithm in algorithms {
+        let mut buf = [0u8; MAX_DIGITS];
+        let (matched, count) = allocations(|| {
+            let value = hotp_value(SECRET, 7, 6, hash_algorithm).unwrap();
+            let code = write_hotp_code(SECRET, 7, 6, hash_algorithm, &mut buf).unwrap();
+            value.matches(code)
+        });
+        assert!(matched);
+        assert_eq!(count, 0, "{}", hash_algorithm);
+    }
+    // RFC 4226 附录D 计数器7
+    let mut buf = [0u8; 6];
+    assert_eq!(
+        write_hotp_code(SECRET, 7, 6, HashAlgorithm::SHA1, &mut buf).unwrap(),
+        "162583"
+    );
+}
+
+/// 测试预先载入密钥的生成器生成与校验均不进行堆分配
+#[test]
+fn test_generator_without_allocation() {
+    let (generator, count) =
+        allocations(|| TotpGenerator::from_bytes(SECRET, config(HashAlgorithm::SM3)).unwrap());
+    assert_eq!(count, 0);
+    let clock = FixedClock::from_unix(1111111109);
+    let mut buf = [0u8; MAX_DIGITS];
+    let (offset, count) = allocations(|| {
+        let expected = generator.code_value(37037036).write_to(&mut buf).unwrap();
+        generator.verify_with_clock(expected, 1, 1, &clock).unwrap()
+    });
+    assert_eq!(offset, 0);
+    assert_eq!(count, 0);
+}
+
+/// 测试缓冲区过小与位数无效的错误
+#[test]
+fn test_buffer_errors() {
+    let mut buf = [0u8; 5];
+    assert!(matches!(
+        write_hotp_code(SECRET, 0, 6, HashAlgorithm::SHA1, &mut buf),
+        Err(TotpError::BufferTooSmall)
+    ));
+    assert!(matches!(
+        hotp_value(SECRET, 0, 11, HashAlgorithm::SHA1),
+        Err(TotpError::InvalidDigits)
+    ));
+
+    // 较大的缓冲区只写入开头部分
+    let mut buf = [b'x'; 12];
+    assert_eq!(
+        write_hotp_code(SECRET, 0, 6, HashAlgorithm::SHA1, &mut buf).unwrap(),
+        "755224"
+    );
+    assert_eq!(&buf[6..], b"xxxxxx");
+}
+
+/// 测试未启用i18n特性时错误信息使用内置英文消息
+#[cfg(not(feature = "i18n"))]
+#[test]
+fn test_error_message_without_i18n() {
+    assert_eq!(
+        TotpError::BufferTooSmall.to_string(),
+        "Output buffer is too small"
+    );
+    assert_eq!(
+        TotpError::CodeMismatch.to_string(),
+        "Verification code does not match"
+    );
+}
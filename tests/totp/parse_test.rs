@@ -0,0 +1,131 @@
+use totp_sm_rs::utils::totp::algorithm::HashAlgorithmError;
+use totp_sm_rs::utils::totp::totp::HashAlgorithm;
+
+/// 测试常见算法名称写法
+#[test]
+fn test_parse_names() {
+    let cases = [
+        ("SHA1", HashAlgorithm::SHA1),
+        ("sha-1", HashAlgorithm::SHA1),
+        ("  hmac_sha1 ", HashAlgorithm::SHA1),
+        ("sha-256", HashAlgorithm::SHA256),
+        ("HMAC-SHA256", HashAlgorithm::SHA256),
+        ("hmacWithSHA256", HashAlgorithm::SHA256),
+        ("HmacSHA512", HashAlgorithm::SHA512),
+        ("SHA-224", HashAlgorithm::SHA224),
+        ("sha384", HashAlgorithm::SHA384),
+        ("SM3", HashAlgorithm::SM3),
+        ("HmacSM3", HashAlgorithm::SM3),
+    ];
+    for (name, expected) in cases {
+        assert_eq!(name.parse(), Ok(expected), "{}", name);
+        assert_eq!(HashAlgorithm::try_from(name), Ok(expected), "{}", name);
+    }
+}
+
+/// 测试每种已启用算法的名称均可往返解析
+#[test]
+fn test_parse_display_round_trip() {
+    for &hash_algorithm in HashAlgorithm::ALL {
+        assert_eq!(hash_algorithm.name().parse(), Ok(hash_algorithm));
+        assert_eq!(
+            hash_algorithm.name().to_lowercase().parse(),
+            Ok(hash_algorithm)
+        );
+    }
+}
+
+/// 测试哈希算法OID与HMAC算法OID
+#[test]
+fn test_parse_oids() {
+    let cases = [
+        ("1.3.14.3.2.26", HashAlgorithm::SHA1),
+        ("1.2.840.113549.2.7", HashAlgorithm::SHA1),
+        ("urn:oid:1.2.840.113549.2.9", HashAlgorithm::SHA256),
+        ("2.16.840.1.101.3.4.2.1", HashAlgorithm::SHA256),
+        ("2.16.840.1.101.3.4.2.4", HashAlgorithm::SHA224),
+        ("1.2.840.113549.2.10", HashAlgorithm::SHA384),
+        ("2.16.840.1.101.3.4.2.3", HashAlgorithm::SHA512),
+        ("1.2.156.10197.1.401", HashAlgorithm::SM3),
+        ("1.2.156.10197.1.401.3.1", HashAlgorithm::SM3),
+    ];
+    for (oid, expected) in cases {
+        assert_eq!(oid.parse(), Ok(expected), "{}", oid);
+    }
+}
+
+/// 测试需要可选特性的算法
+#[test]
+fn test_parse_feature_gated() {
+    #[cfg(feature = "sha3")]
+    {
+        assert_eq!("SHA3-256".parse(), Ok(HashAlgorithm::SHA3_256));
+        assert_eq!("HmacSHA3-512".parse(), Ok(HashAlgorithm::SHA3_512));
+        assert_eq!(
+            "2.16.840.1.101.3.4.2.14".parse(),
+            Ok(HashAlgorithm::SHA3_256)
+        );
+    }
+    #[cfg(not(feature = "sha3"))]
+    assert_eq!(
+        "SHA3-256".parse::<HashAlgorithm>(),
+        Err(HashAlgorithmError::FeatureDisabled("sha3"))
+    );
+
+    #[cfg(feature = "blake2")]
+    {
+        assert_eq!("blake2b512".parse(), Ok(HashAlgorithm::BLAKE2B));
+        assert_eq!(
+            "1.3.6.1.4.1.1722.12.2.2.8".parse(),
+            Ok(HashAlgorithm::BLAKE2S)
+        );
+    }
+    #[cfg(not(feature = "blake2"))]
+    assert_eq!(
+        "BLAKE2S".parse::<HashAlgorithm>(),
+        Err(HashAlgorithmError::FeatureDisabled("blake2"))
+    );
+
+    #[cfg(feature = "streebog")]
+    {
+        assert_eq!(
+            "GOST R 34.11-2012-256".parse(),
+            Ok(HashAlgorithm::STREEBOG256)
+        );
+        assert_eq!("1.2.643.7.1.1.4.2".parse(), Ok(HashAlgorithm::STREEBOG512));
+    }
+    #[cfg(not(feature = "streebog"))]
+    assert_eq!(
+        "1.2.643.7.1.1.2.2".parse::<HashAlgorithm>(),
+        Err(HashAlgorithmError::FeatureDisabled("streebog"))
+    );
+}
+
+/// 测试无效输入的错误类型
+#[test]
+fn test_parse_errors() {
+    let cases = [
+        ("", HashAlgorithmError::Empty),
+        ("   ", HashAlgorithmError::Empty),
+        ("SHA2", HashAlgorithmError::UnknownName),
+        ("HMAC", HashAlgorithmError::UnknownName),
+        ("with-sha256", HashAlgorithmError::UnknownName),
+        ("SHA256!", HashAlgorithmError::UnknownName),
+        ("1.2.3.4", HashAlgorithmError::UnknownOid),
+        ("MD5", HashAlgorithmError::Unsupported("MD5")),
+        ("HmacMD5", HashAlgorithmError::Unsupported("MD5")),
+        ("1.2.840.113549.2.5", HashAlgorithmError::Unsupported("MD5")),
+        ("RIPEMD-160", HashAlgorithmError::Unsupported("RIPEMD160")),
+        ("SHA-512/256", HashAlgorithmError::Unsupported("SHA512/256")),
+        (
+            "2.16.840.1.101.3.4.2.5",
+            HashAlgorithmError::Unsupported("SHA512/224"),
+        ),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(input.parse::<HashAlgorithm>(), Err(expected), "{}", input);
+    }
+    assert!(HashAlgorithmError::Unsupported("MD5")
+        .to_string()
+        .ends_with(": MD5"));
+}
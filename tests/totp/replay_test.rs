@@ -0,0 +1,127 @@
+use totp_sm_rs::utils::totp::replay::{verify_totp_once, InMemoryReplayStore, ReplayStore};
+use totp_sm_rs::utils::totp::totp::{generate_totp_code, TotpConfig, TotpError};
+
+const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+fn config_at(timestamp: i64) -> TotpConfig {
+    TotpConfig {
+        digits: 8,
+        timestamp: Some(timestamp),
+        ..TotpConfig::default()
+    }
+}
+
+/// 测试同一验证码只能被接受一次
+#[test]
+fn test_code_accepted_once() {
+    let store = InMemoryReplayStore::new();
+    let code = generate_totp_code(SECRET, Some(config_at(1111111109))).unwrap();
+
+    let offset = verify_totp_once(
+        SECRET,
+        &code,
+        Some(config_at(1111111109)),
+        1,
+        1,
+        "alice",
+        &store,
+    )
+    .unwrap();
+    assert_eq!(offset, 0);
+    assert_eq!(store.last_used_step("alice"), Some(1111111109 / 30));
+
+    // 同一时间窗口内再次提交应被拒绝
+    let result = verify_totp_once(
+        SECRET,
+        &code,
+        Some(config_at(1111111110)),
+        1,
+        1,
+        "alice",
+        &store,
+    );
+    assert!(matches!(result, Err(TotpError::ReplayedCode)));
+
+    // 其他凭据不受影响
+    assert!(verify_totp_once(
+        SECRET,
+        &code,
+        Some(config_at(1111111109)),
+        1,
+        1,
+        "bob",
+        &store
+    )
+    .is_ok());
+}
+
+/// 测试已接受时间步之前的验证码被拒绝
+#[test]
+fn test_earlier_step_rejected() {
+    let store = InMemoryReplayStore::new();
+    let previous = generate_totp_code(SECRET, Some(config_at(1111111109 - 30))).unwrap();
+    let current = generate_totp_code(SECRET, Some(config_at(1111111109))).unwrap();
+
+    verify_totp_once(
+        SECRET,
+        &current,
+        Some(config_at(1111111109)),
+        1,
+        1,
+        "alice",
+        &store,
+    )
+    .unwrap();
+    let result = verify_totp_once(
+        SECRET,
+        &previous,
+        Some(config_at(1111111109)),
+        1,
+        1,
+        "alice",
+        &store,
+    );
+    assert!(matches!(result, Err(TotpError::ReplayedCode)));
+
+    // 下一个时间步的验证码仍然可以使用
+    let next = generate_totp_code(SECRET, Some(config_at(1111111109 + 30))).unwrap();
+    let offset = verify_totp_once(
+        SECRET,
+        &next,
+        Some(config_at(1111111109)),
+        1,
+        1,
+        "alice",
+        &store,
+    )
+    .unwrap();
+    assert_eq!(offset, 1);
+}
+
+/// 测试错误验证码不会更新存储
+#[test]
+fn test_mismatch_does_not_record() {
+    let store = InMemoryReplayStore::new();
+    let result = verify_totp_once(
+        SECRET,
+        "00000000",
+        Some(config_at(1111111109)),
+        1,
+        1,
+        "alice",
+        &store,
+    );
+    assert!(matches!(result, Err(TotpError::CodeMismatch)));
+    assert_eq!(store.last_used_step("alice"), None);
+}
+
+/// 测试内存存储只接受递增的时间步
+#[test]
+fn test_in_memory_store_monotonic() {
+    let store = InMemoryReplayStore::new();
+    assert!(store.mark_used_step("alice", 10));
+    assert!(!store.mark_used_step("alice", 10));
+    assert!(!store.mark_used_step("alice", 9));
+    assert!(store.mark_used_step("alice", 11));
+    assert_eq!(store.last_used_step("alice"), Some(11));
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+use tokio::time::Instant;
+use tokio_stream::StreamExt;
+use totp_sm_rs::utils::totp::clock::{Clock, FixedClock};
+use totp_sm_rs::utils::totp::stream::totp_stream_with_clock;
+use totp_sm_rs::utils::totp::totp::{TotpConfig, TotpError};
+
+const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+/// 跟随Tokio（可暂停）时间前进的时钟
+struct TokioClock {
+    base: Duration,
+    start: Instant,
+}
+
+impl TokioClock {
+    fn new(base: Duration) -> Self {
+        Self {
+            base,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for TokioClock {
+    fn now(&self) -> Duration {
+        self.base + self.start.elapsed()
+    }
+}
+
+fn config() -> Option<TotpConfig> {
+    Some(TotpConfig {
+        digits: 8,
+        ..TotpConfig::default()
+    })
+}
+
+/// 测试首项立即产出，此后恰好在每个时间步开始时产出新验证码
+#[tokio::test(start_paused = true)]
+async fn test_stream_ticks_at_boundaries() {
+    let start = Instant::now();
+    let clock = TokioClock::new(Duration::from_secs(1111111109));
+    let mut stream = totp_stream_with_clock(SECRET, config(), clock).unwrap();
+
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.code(), "07081804");
+    assert_eq!(start.elapsed(), Duration::ZERO);
+
+    let second = stream.next().await.unwrap().unwrap();
+    assert_eq!(second.code(), "14050471");
+    assert_eq!(second.counter(), first.counter() + 1);
+    assert_eq!(second.remaining(), Duration::from_secs(30));
+    assert_eq!(start.elapsed(), Duration::from_secs(1));
+
+    let third = stream.next().await.unwrap().unwrap();
+    assert_eq!(third.counter(), second.counter() + 1);
+    assert_eq!(start.elapsed(), Duration::from_secs(31));
+}
+
+/// 测试配置无效时无法创建流，时间早于T0时产出错误后结束
+#[tokio::test(start_paused = true)]
+async fn test_stream_errors() {
+    let invalid = Some(TotpConfig {
+        time_step: Duration::ZERO,
+        ..TotpConfig::default()
+    });
+    assert!(matches!(
+        totp_stream_with_clock(SECRET, invalid, FixedClock::from_unix(0)),
+        Err(TotpError::InvalidTimeStep)
+    ));
+
+    let config = Some(TotpConfig {
+        t0: 100,
+        ..TotpConfig::default()
+    });
+    let mut stream = totp_stream_with_clock(SECRET, config, FixedClock::from_unix(50)).unwrap();
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(TotpError::InvalidTimestamp))
+    ));
+    assert!(stream.next().await.is_none());
+}
@@ -1,4 +1,11 @@
-use totp_sm_rs::utils::totp::totp::{generate_totp_code, HashAlgorithm, TotpConfig, TotpError};
+use sha1::Sha1;
+use std::time::Duration;
+use totp_sm_rs::utils::hotp::hotp::{generate_hotp_code, hotp_value};
+use totp_sm_rs::utils::totp::clock::SystemClock;
+use totp_sm_rs::utils::totp::generator::TotpGenerator;
+use totp_sm_rs::utils::totp::totp::{
+    generate_totp_code, verify_totp, verify_totp_with_digest, HashAlgorithm, TotpConfig, TotpError,
+};
 const RFC_TEST_VECTORS: [(&str, i64, &str, HashAlgorithm); 24] = [
     // SHA-1 测试向量
     ("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ", 59, "94287082", HashAlgorithm::SHA1),
@@ -39,7 +46,8 @@ fn test_rfc_test_vectors() {
     for (secret, timestamp, expected, hash_algorithm) in RFC_TEST_VECTORS {
         let config = TotpConfig {
             digits: 8,
-            time_step: 30,
+            time_step: Duration::from_secs(30),
+            t0: 0,
             timestamp: Some(timestamp),
             timezone_offset: None,
             hash_algorithm,
@@ -71,7 +79,8 @@ fn test_invalid_base32() {
     for secret in invalid_secrets {
         let config = TotpConfig {
             digits: 8,
-            time_step: 30,
+            time_step: Duration::from_secs(30),
+            t0: 0,
             timestamp: None,
             timezone_offset: None,
             hash_algorithm: HashAlgorithm::SHA1,
@@ -92,7 +101,8 @@ fn test_key_length_validation() {
     let short_secret = "GEZDG"; // 过短
     let config = TotpConfig {
         digits: 8,
-        time_step: 30,
+        time_step: Duration::from_secs(30),
+        t0: 0,
         timestamp: None,
         timezone_offset: None,
         hash_algorithm: HashAlgorithm::SHA1,
@@ -108,7 +118,8 @@ fn test_code_length() {
     let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
     let config = TotpConfig {
         digits: 8,
-        time_step: 30,
+        time_step: Duration::from_secs(30),
+        t0: 0,
         timestamp: Some(1234567890),
         timezone_offset: None,
         hash_algorithm: HashAlgorithm::SHA1,
@@ -124,7 +135,8 @@ fn test_code_range() {
     let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
     let config = TotpConfig {
         digits: 8,
-        time_step: 30,
+        time_step: Duration::from_secs(30),
+        t0: 0,
         timestamp: Some(1234567890),
         timezone_offset: None,
         hash_algorithm: HashAlgorithm::SHA1,
@@ -143,7 +155,8 @@ fn test_time_window_change() {
     // 测试相同时间窗口
     let config1 = TotpConfig {
         digits: 8,
-        time_step: 30,
+        time_step: Duration::from_secs(30),
+        t0: 0,
         timestamp: Some(1234567890),
         timezone_offset: None,
         hash_algorithm: HashAlgorithm::SHA1,
@@ -153,7 +166,8 @@ fn test_time_window_change() {
 
     let config2 = TotpConfig {
         digits: 8,
-        time_step: 30,
+        time_step: Duration::from_secs(30),
+        t0: 0,
         timestamp: Some(1234567895),
         timezone_offset: None,
         hash_algorithm: HashAlgorithm::SHA1,
@@ -165,7 +179,8 @@ fn test_time_window_change() {
     // 测试不同时间窗口
     let config3 = TotpConfig {
         digits: 8,
-        time_step: 30,
+        time_step: Duration::from_secs(30),
+        t0: 0,
         timestamp: Some(1234567920),
         timezone_offset: None,
         hash_algorithm: HashAlgorithm::SHA1,
@@ -183,7 +198,8 @@ fn test_edge_cases() {
     // 测试时间戳为0
     let config1 = TotpConfig {
         digits: 8,
-        time_step: 30,
+        time_step: Duration::from_secs(30),
+        t0: 0,
         timestamp: Some(0),
         timezone_offset: None,
         hash_algorithm: HashAlgorithm::SHA1,
@@ -195,7 +211,8 @@ fn test_edge_cases() {
     // 测试最大时间戳
     let config2 = TotpConfig {
         digits: 8,
-        time_step: 30,
+        time_step: Duration::from_secs(30),
+        t0: 0,
         timestamp: Some(i64::MAX),
         timezone_offset: None,
         hash_algorithm: HashAlgorithm::SHA1,
@@ -221,7 +238,8 @@ fn test_different_digits() {
         for &algorithm in &hash_algorithms {
             let config = TotpConfig {
                 digits,
-                time_step: 30,
+                time_step: Duration::from_secs(30),
+                t0: 0,
                 timestamp: None,
                 timezone_offset: None,
                 hash_algorithm: algorithm,
@@ -261,7 +279,8 @@ fn test_performance() {
     for &algorithm in &hash_algorithms {
         let config = TotpConfig {
             digits: 6,
-            time_step: 30,
+            time_step: Duration::from_secs(30),
+            t0: 0,
             timestamp: Some(1234567890),
             timezone_offset: None,
             hash_algorithm: algorithm,
@@ -291,7 +310,8 @@ fn test_default_parameters() {
     // 测试默认digits (6)
     let config = TotpConfig {
         digits: 6,
-        time_step: 30,
+        time_step: Duration::from_secs(30),
+        t0: 0,
         timestamp: Some(1234567890),
         timezone_offset: None,
         hash_algorithm: HashAlgorithm::SHA1,
@@ -303,7 +323,8 @@ fn test_default_parameters() {
     // 测试默认time_step (30)
     let config = TotpConfig {
         digits: 8,
-        time_step: 30,
+        time_step: Duration::from_secs(30),
+        t0: 0,
         timestamp: Some(1234567890),
         timezone_offset: None,
         hash_algorithm: HashAlgorithm::SHA1,
@@ -316,3 +337,239 @@ fn test_default_parameters() {
     let code = generate_totp_code(secret, None).unwrap();
     assert_eq!(code.len(), 6);
 }
+
+/// 测试校验当前时间步的验证码
+#[test]
+fn test_verify_current_step() {
+    let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    let config = TotpConfig {
+        digits: 8,
+        timestamp: Some(59),
+        ..TotpConfig::default()
+    };
+    let offset = verify_totp(secret, "94287082", Some(config), 1, 1).unwrap();
+    assert_eq!(offset, 0);
+}
+
+/// 测试校验窗口内前后时间步的验证码
+#[test]
+fn test_verify_skew_window() {
+    let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    let at = |timestamp| TotpConfig {
+        digits: 8,
+        timestamp: Some(timestamp),
+        ..TotpConfig::default()
+    };
+    let previous = generate_totp_code(secret, Some(at(1111111109 - 30))).unwrap();
+    let next = generate_totp_code(secret, Some(at(1111111109 + 60))).unwrap();
+
+    assert_eq!(
+        verify_totp(secret, &previous, Some(at(1111111109)), 1, 0).unwrap(),
+        -1
+    );
+    assert_eq!(
+        verify_totp(secret, &next, Some(at(1111111109)), 0, 2).unwrap(),
+        2
+    );
+
+    // 超出窗口的验证码应当被拒绝
+    assert!(matches!(
+        verify_totp(secret, &previous, Some(at(1111111109)), 0, 1),
+        Err(TotpError::CodeMismatch)
+    ));
+    assert!(matches!(
+        verify_totp(secret, &next, Some(at(1111111109)), 1, 1),
+        Err(TotpError::CodeMismatch)
+    ));
+}
+
+/// 测试校验错误的验证码
+#[test]
+fn test_verify_wrong_code() {
+    let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    let config = TotpConfig {
+        digits: 8,
+        timestamp: Some(59),
+        ..TotpConfig::default()
+    };
+    for code in ["00000000", "9428708", "942870820", ""] {
+        assert!(matches!(
+            verify_totp(secret, code, Some(config), 2, 2),
+            Err(TotpError::CodeMismatch)
+        ));
+    }
+}
+
+/// 测试配置构建器校验全部字段
+#[test]
+fn test_config_builder() {
+    let config = TotpConfig::builder()
+        .digits(8)
+        .time_step(Duration::from_secs(30))
+        .timestamp(59)
+        .hash_algorithm(HashAlgorithm::SHA1)
+        .check_security(true)
+        .build()
+        .unwrap();
+    assert_eq!(
+        generate_totp_code("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ", Some(config)).unwrap(),
+        "94287082"
+    );
+
+    for digits in [0, 3, 11] {
+        assert!(matches!(
+            TotpConfig::builder().digits(digits).build(),
+            Err(TotpError::InvalidDigits)
+        ));
+    }
+    assert!(matches!(
+        TotpConfig::builder().time_step(Duration::ZERO).build(),
+        Err(TotpError::InvalidTimeStep)
+    ));
+    assert!(matches!(
+        TotpConfig::builder().timestamp(-1).build(),
+        Err(TotpError::InvalidTimestamp)
+    ));
+}
+
+/// 测试未经构建器的无效配置同样被拒绝，而非引发除零恐慌
+#[test]
+fn test_invalid_config_rejected() {
+    let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    let config = TotpConfig {
+        digits: 3,
+        ..TotpConfig::default()
+    };
+    assert!(matches!(
+        generate_totp_code(secret, Some(config)),
+        Err(TotpError::InvalidDigits)
+    ));
+    assert!(matches!(
+        verify_totp(secret, "000000", Some(config), 1, 1),
+        Err(TotpError::InvalidDigits)
+    ));
+
+    let config = TotpConfig {
+        time_step: Duration::from_secs(0),
+        ..TotpConfig::default()
+    };
+    assert!(matches!(
+        generate_totp_code(secret, Some(config)),
+        Err(TotpError::InvalidTimeStep)
+    ));
+    assert!(matches!(
+        verify_totp(secret, "000000", Some(config), 1, 1),
+        Err(TotpError::InvalidTimeStep)
+    ));
+
+    let config = TotpConfig {
+        timestamp: Some(10),
+        timezone_offset: Some(-20),
+        ..TotpConfig::default()
+    };
+    assert!(matches!(
+        generate_totp_code(secret, Some(config)),
+        Err(TotpError::InvalidTimestamp)
+    ));
+    assert!(matches!(
+        verify_totp(secret, "000000", Some(config), 1, 1),
+        Err(TotpError::InvalidTimestamp)
+    ));
+}
+
+/// 测试小时、天级及亚秒级时间步长
+#[test]
+fn test_wide_and_sub_second_time_steps() {
+    let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    let cases = [
+        (Duration::from_secs(3600), 1234567890, 342935),
+        (Duration::from_secs(86400), 1234567890, 14288),
+        (Duration::from_millis(100), 5, 50),
+    ];
+    for (time_step, timestamp, counter) in cases {
+        let config = TotpConfig::builder()
+            .digits(8)
+            .time_step(time_step)
+            .timestamp(timestamp)
+            .build()
+            .unwrap();
+        assert_eq!(
+            generate_totp_code(secret, Some(config)).unwrap(),
+            generate_hotp_code(secret, counter, 8, HashAlgorithm::SHA1).unwrap(),
+            "{:?}",
+            time_step
+        );
+    }
+}
+
+/// 测试自定义T0及早于T0的时间戳被拒绝
+#[test]
+fn test_custom_t0() {
+    let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    let config = TotpConfig::builder()
+        .digits(8)
+        .t0(1_000_000)
+        .timestamp(1_000_059)
+        .build()
+        .unwrap();
+    assert_eq!(
+        generate_totp_code(secret, Some(config)).unwrap(),
+        "94287082"
+    );
+    assert_eq!(
+        verify_totp(secret, "94287082", Some(config), 0, 0).unwrap(),
+        0
+    );
+
+    assert!(matches!(
+        TotpConfig::builder()
+            .t0(1_000_000)
+            .timestamp(999_999)
+            .build(),
+        Err(TotpError::InvalidTimestamp)
+    ));
+    let config = TotpConfig {
+        timezone_offset: Some(-60),
+        ..config
+    };
+    assert!(matches!(
+        generate_totp_code(secret, Some(config)),
+        Err(TotpError::InvalidTimestamp)
+    ));
+}
+
+/// 测试T0时刻向前回溯不会把负数时间步当作计数器（-1曾被转换为u64::MAX）
+#[test]
+fn test_verify_at_t0_skips_negative_steps() {
+    let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    let config = TotpConfig::builder()
+        .digits(8)
+        .t0(1_000_000)
+        .timestamp(1_000_000)
+        .build()
+        .unwrap();
+    let bogus = hotp_value(b"12345678901234567890", u64::MAX, 8, HashAlgorithm::SHA1)
+        .unwrap()
+        .to_string();
+    let current = generate_totp_code(secret, Some(config)).unwrap();
+    assert_ne!(bogus, current);
+
+    assert_eq!(
+        verify_totp(secret, &current, Some(config), 2, 0).unwrap(),
+        0
+    );
+    assert!(matches!(
+        verify_totp(secret, &bogus, Some(config), 2, 0),
+        Err(TotpError::CodeMismatch)
+    ));
+    assert!(matches!(
+        verify_totp_with_digest::<Sha1>(secret, &bogus, Some(config), 2, 0, &SystemClock),
+        Err(TotpError::CodeMismatch)
+    ));
+    let generator = TotpGenerator::new(secret, Some(config)).unwrap();
+    assert!(matches!(
+        generator.verify(&bogus, 2, 0),
+        Err(TotpError::CodeMismatch)
+    ));
+}
@@ -0,0 +1,285 @@
+use std::time::Duration;
+use totp_sm_rs::utils::secret::secret::Secret;
+use totp_sm_rs::utils::totp::totp::{generate_totp_code, HashAlgorithm, TotpConfig, TotpError};
+use totp_sm_rs::utils::uri::uri::{OtpAuthUri, OtpType, ParseMode, UriError};
+
+const SECRET: &str = "JBSWY3DPEHPK3PXP";
+
+fn lenient(uri: &str) -> Result<OtpAuthUri, UriError> {
+    OtpAuthUri::parse(uri, ParseMode::Lenient)
+}
+
+/// 测试解析完整的TOTP URI并转换为TotpConfig
+#[test]
+fn test_parse_totp() {
+    let uri: OtpAuthUri =
+        "otpauth://totp/ACME%20Co:alice%40example.com?secret=JBSWY3DPEHPK3PXP&issuer=ACME%20Co&algorithm=SHA256&digits=8&period=60"
+            .parse()
+            .unwrap();
+    assert_eq!(uri.otp_type, OtpType::Totp { period: 60 });
+    assert_eq!(uri.issuer.as_deref(), Some("ACME Co"));
+    assert_eq!(uri.account_name, "alice@example.com");
+    assert_eq!(uri.secret.to_base32(), SECRET);
+    assert_eq!(uri.algorithm, HashAlgorithm::SHA256);
+    assert_eq!(uri.digits, 8);
+
+    let config = uri.totp_config().unwrap();
+    assert_eq!(config.time_step, Duration::from_secs(60));
+    assert_eq!(config.digits, 8);
+    assert_eq!(config.hash_algorithm, HashAlgorithm::SHA256);
+    assert_eq!(
+        generate_totp_code(&uri.secret, Some(config)).unwrap().len(),
+        8
+    );
+}
+
+/// 测试调试输出隐藏密钥，以及构造时拒绝无效密钥
+#[test]
+fn test_debug_redacts_secret() {
+    let uri: OtpAuthUri = format!("otpauth://totp/alice?secret={}", SECRET)
+        .parse()
+        .unwrap();
+    let debug = format!("{:?}", uri);
+    assert!(!debug.contains(SECRET));
+    assert!(debug.contains("REDACTED"));
+
+    let secret = Secret::from_base32(SECRET).unwrap();
+    let built = OtpAuthUri::new_totp(None, "alice", &secret, &TotpConfig::default()).unwrap();
+    assert_eq!(built.secret, secret);
+    assert!(matches!(
+        OtpAuthUri::new_totp(None, "alice", "not base32!", &TotpConfig::default()),
+        Err(TotpError::Base32DecodeError)
+    ));
+}
+
+/// 测试缺省参数以及SM3扩展算法
+#[test]
+fn test_defaults_and_sm3() {
+    let uri: OtpAuthUri = "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP"
+        .parse()
+        .unwrap();
+    assert_eq!(uri.otp_type, OtpType::Totp { period: 30 });
+    assert_eq!(uri.issuer, None);
+    assert_eq!(uri.algorithm, HashAlgorithm::SHA1);
+    assert_eq!(uri.digits, 6);
+
+    let uri: OtpAuthUri = "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP&algorithm=SM3"
+        .parse()
+        .unwrap();
+    assert_eq!(uri.algorithm, HashAlgorithm::SM3);
+
+    // 超过255秒的时间步长（如按天）
+    let uri: OtpAuthUri = "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP&period=86400"
+        .parse()
+        .unwrap();
+    assert_eq!(uri.otp_type, OtpType::Totp { period: 86400 });
+    assert_eq!(
+        uri.totp_config().unwrap().time_step,
+        Duration::from_secs(86400)
+    );
+}
+
+/// 测试TOTP与HOTP URI的生成与往返解析
+#[test]
+fn test_round_trip() {
+    let config = TotpConfig {
+        digits: 8,
+        time_step: Duration::from_secs(60),
+        hash_algorithm: HashAlgorithm::SM3,
+        ..TotpConfig::default()
+    };
+    let totp = OtpAuthUri::new_totp(Some("ACME: Co"), "alice smith", SECRET, &config).unwrap();
+    let text = totp.to_string();
+    assert_eq!(
+        text,
+        "otpauth://totp/ACME%3A%20Co:alice%20smith?secret=JBSWY3DPEHPK3PXP&issuer=ACME%3A%20Co&algorithm=SM3&digits=8&period=60"
+    );
+    assert_eq!(text.parse::<OtpAuthUri>().unwrap(), totp);
+
+    let hotp = OtpAuthUri::new_hotp(None, "bob", SECRET, 42, 6, HashAlgorithm::SHA512).unwrap();
+    let text = hotp.to_string();
+    assert_eq!(
+        text,
+        "otpauth://hotp/bob?secret=JBSWY3DPEHPK3PXP&algorithm=SHA512&digits=6&counter=42"
+    );
+    let parsed: OtpAuthUri = text.parse().unwrap();
+    assert_eq!(parsed, hotp);
+    assert!(parsed.totp_config().is_none());
+}
+
+/// 测试构造出的URI（各哈希算法与位数）输出后可被严格模式原样解析
+#[test]
+fn test_constructed_uri_parses_strictly() {
+    for &hash_algorithm in HashAlgorithm::ALL {
+        for digits in 4..=10 {
+            let config = TotpConfig {
+                digits,
+                time_step: Duration::from_secs(45),
+                hash_algorithm,
+                ..TotpConfig::default()
+            };
+            let totp = OtpAuthUri::new_totp(Some("ACME"), "alice", SECRET, &config).unwrap();
+            let parsed = OtpAuthUri::parse(&totp.to_string(), ParseMode::Strict).unwrap();
+            assert_eq!(parsed, totp, "{} {}", hash_algorithm, digits);
+
+            let hotp =
+                OtpAuthUri::new_hotp(Some("ACME"), "alice", SECRET, 7, digits, hash_algorithm)
+                    .unwrap();
+            let parsed = OtpAuthUri::parse(&hotp.to_string(), ParseMode::Strict).unwrap();
+            assert_eq!(parsed, hotp, "{} {}", hash_algorithm, digits);
+        }
+    }
+}
+
+/// 测试构造函数拒绝URI无法表示的位数与时间步长
+#[test]
+fn test_constructor_validation() {
+    let config = |digits, time_step| TotpConfig {
+        digits,
+        time_step,
+        ..TotpConfig::default()
+    };
+    assert!(matches!(
+        OtpAuthUri::new_totp(None, "alice", SECRET, &config(3, Duration::from_secs(30))),
+        Err(TotpError::InvalidDigits)
+    ));
+    assert!(matches!(
+        OtpAuthUri::new_totp(
+            None,
+            "alice",
+            SECRET,
+            &config(6, Duration::from_millis(500))
+        ),
+        Err(TotpError::InvalidTimeStep)
+    ));
+    assert!(matches!(
+        OtpAuthUri::new_totp(
+            None,
+            "alice",
+            SECRET,
+            &config(6, Duration::from_secs(u32::MAX as u64 + 1))
+        ),
+        Err(TotpError::InvalidTimeStep)
+    ));
+    assert!(matches!(
+        OtpAuthUri::new_hotp(None, "alice", SECRET, 0, 11, HashAlgorithm::SHA1),
+        Err(TotpError::InvalidDigits)
+    ));
+}
+
+/// 测试严格模式对不规范URI的拒绝
+#[test]
+fn test_strict_errors() {
+    let cases = [
+        (
+            "https://totp/alice?secret=JBSWY3DPEHPK3PXP",
+            UriError::InvalidScheme,
+        ),
+        (
+            "otpauth://motp/alice?secret=JBSWY3DPEHPK3PXP",
+            UriError::InvalidType("motp".to_string()),
+        ),
+        (
+            "otpauth://totp/?secret=JBSWY3DPEHPK3PXP",
+            UriError::InvalidLabel,
+        ),
+        ("otpauth://totp/alice", UriError::MissingParameter("secret")),
+        (
+            "otpauth://totp/alice?secret=jbswy3dpehpk3pxp",
+            UriError::InvalidParameter("secret"),
+        ),
+        (
+            "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP&algorithm=sha256",
+            UriError::InvalidParameter("algorithm"),
+        ),
+        (
+            "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP&digits=12",
+            UriError::InvalidParameter("digits"),
+        ),
+        (
+            "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP&period=0",
+            UriError::InvalidParameter("period"),
+        ),
+        (
+            "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP&image=x",
+            UriError::UnknownParameter("image".to_string()),
+        ),
+        (
+            "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP&digits=6&digits=8",
+            UriError::DuplicateParameter("digits".to_string()),
+        ),
+        (
+            "otpauth://totp/ACME:alice?secret=JBSWY3DPEHPK3PXP&issuer=Other",
+            UriError::IssuerMismatch,
+        ),
+        (
+            "otpauth://hotp/alice?secret=JBSWY3DPEHPK3PXP",
+            UriError::MissingParameter("counter"),
+        ),
+        (
+            "otpauth://hotp/alice?secret=JBSWY3DPEHPK3PXP&counter=-1",
+            UriError::InvalidParameter("counter"),
+        ),
+    ];
+    for (uri, expected) in cases {
+        assert_eq!(uri.parse::<OtpAuthUri>().unwrap_err(), expected, "{}", uri);
+    }
+}
+
+/// 测试宽松模式对常见不规范写法的容忍
+#[test]
+fn test_lenient_parsing() {
+    let uri = lenient(
+        "OTPAUTH://TOTP/ACME:alice?secret=jbsw%20y3dp%20ehpk%203pxp%3D%3D&issuer=Big+Corp&algorithm=sha-256&image=x&digits=8&digits=6",
+    )
+    .unwrap();
+    assert_eq!(uri.secret.to_base32(), SECRET);
+    assert_eq!(uri.issuer.as_deref(), Some("Big Corp"));
+    assert_eq!(uri.algorithm, HashAlgorithm::SHA256);
+    assert_eq!(uri.digits, 8);
+
+    // 宽松模式接受HashAlgorithm支持的全部写法
+    for (name, expected) in [
+        ("HmacSHA512", HashAlgorithm::SHA512),
+        ("sha-384", HashAlgorithm::SHA384),
+        ("sha_224", HashAlgorithm::SHA224),
+        ("sm3", HashAlgorithm::SM3),
+    ] {
+        let uri = lenient(&format!(
+            "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP&algorithm={}",
+            name
+        ))
+        .unwrap();
+        assert_eq!(uri.algorithm, expected, "{}", name);
+    }
+    #[cfg(feature = "sha3")]
+    assert_eq!(
+        lenient("otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP&algorithm=sha3_256")
+            .unwrap()
+            .algorithm,
+        HashAlgorithm::SHA3_256
+    );
+
+    let hotp = lenient("otpauth://hotp/alice?secret=JBSWY3DPEHPK3PXP").unwrap();
+    assert_eq!(hotp.otp_type, OtpType::Hotp { counter: 0 });
+
+    // 宽松模式仍然拒绝无法使用的参数值
+    assert_eq!(
+        lenient("otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP&digits=eight").unwrap_err(),
+        UriError::InvalidParameter("digits")
+    );
+    assert_eq!(
+        lenient("otpauth://totp/alice?secret=!!!").unwrap_err(),
+        UriError::InvalidParameter("secret")
+    );
+}
+
+/// 测试标签中以 %3A 编码的发行方分隔符
+#[test]
+fn test_encoded_label_separator() {
+    let uri: OtpAuthUri = "otpauth://totp/ACME%3A%20alice?secret=JBSWY3DPEHPK3PXP"
+        .parse()
+        .unwrap();
+    assert_eq!(uri.issuer.as_deref(), Some("ACME"));
+    assert_eq!(uri.account_name, "alice");
+}